@@ -0,0 +1,43 @@
+// Compares the batch helpers with a naive per-point loop
+// cargo bench --bench batch [--features simd]
+use std::time::Instant;
+use salg::batch::{add_all, scale_all};
+use salg::vector::vector3::Vec3;
+
+const POINTS: usize = 1_000_000;
+const ROUNDS: u32 = 20;
+
+fn cloud() -> Vec<Vec3> {
+    (0..POINTS).map(|i| {
+        let t = i as f64;
+        Vec3::new(t, t * 0.5, -t)
+    }).collect()
+}
+
+fn time<F: FnMut(&mut [Vec3])>(name: &str, mut f: F) {
+    let mut points = cloud();
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        f(&mut points);
+    }
+    let elapsed = start.elapsed() / ROUNDS;
+    // Keeps the work from being optimized away
+    let checksum: f64 = points.iter().map(|p| p.x + p.y + p.z).sum();
+    println!("{:<16} {:>10.3?} per round (checksum {:e})", name, elapsed, checksum);
+}
+
+fn main() {
+    let delta = Vec3::new(0.5, -1.0, 2.0);
+    time("naive add", |points| {
+        for p in points.iter_mut() {
+            *p += delta;
+        }
+    });
+    time("add_all", |points| add_all(points, delta));
+    time("naive scale", |points| {
+        for p in points.iter_mut() {
+            *p *= 0.999;
+        }
+    });
+    time("scale_all", |points| scale_all(points, 0.999));
+}
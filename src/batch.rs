@@ -0,0 +1,121 @@
+// Operations over whole slices of points (e.g. point clouds)
+// With the `simd` feature points are processed four at a time so the
+// compiler can vectorize the loop, without it a plain loop is used
+use crate::vector::vector3::Vec3;
+
+// Translates every point by delta
+pub fn add_all(points: &mut [Vec3], delta: Vec3) {
+    imp::add_all(points, delta)
+}
+
+// Scales every point by s (around the origin)
+pub fn scale_all(points: &mut [Vec3], s: f64) {
+    imp::scale_all(points, s)
+}
+
+#[cfg(not(feature = "simd"))]
+mod imp {
+    use crate::vector::vector3::Vec3;
+
+    pub fn add_all(points: &mut [Vec3], delta: Vec3) {
+        for p in points.iter_mut() {
+            *p += delta;
+        }
+    }
+
+    pub fn scale_all(points: &mut [Vec3], s: f64) {
+        for p in points.iter_mut() {
+            *p *= s;
+        }
+    }
+}
+
+#[cfg(feature = "simd")]
+mod imp {
+    use crate::vector::vector3::Vec3;
+
+    const LANES: usize = 4;
+
+    pub fn add_all(points: &mut [Vec3], delta: Vec3) {
+        let mut chunks = points.chunks_exact_mut(LANES);
+        for chunk in &mut chunks {
+            let mut xs = [chunk[0].x, chunk[1].x, chunk[2].x, chunk[3].x];
+            let mut ys = [chunk[0].y, chunk[1].y, chunk[2].y, chunk[3].y];
+            let mut zs = [chunk[0].z, chunk[1].z, chunk[2].z, chunk[3].z];
+            for i in 0..LANES {
+                xs[i] += delta.x;
+                ys[i] += delta.y;
+                zs[i] += delta.z;
+            }
+            for (i, p) in chunk.iter_mut().enumerate() {
+                *p = Vec3::new(xs[i], ys[i], zs[i]);
+            }
+        }
+        for p in chunks.into_remainder() {
+            *p += delta;
+        }
+    }
+
+    pub fn scale_all(points: &mut [Vec3], s: f64) {
+        let mut chunks = points.chunks_exact_mut(LANES);
+        for chunk in &mut chunks {
+            let mut xs = [chunk[0].x, chunk[1].x, chunk[2].x, chunk[3].x];
+            let mut ys = [chunk[0].y, chunk[1].y, chunk[2].y, chunk[3].y];
+            let mut zs = [chunk[0].z, chunk[1].z, chunk[2].z, chunk[3].z];
+            for i in 0..LANES {
+                xs[i] *= s;
+                ys[i] *= s;
+                zs[i] *= s;
+            }
+            for (i, p) in chunk.iter_mut().enumerate() {
+                *p = Vec3::new(xs[i], ys[i], zs[i]);
+            }
+        }
+        for p in chunks.into_remainder() {
+            *p *= s;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{add_all, scale_all};
+    use crate::vector::vector3::Vec3;
+
+    // Odd length so the remainder of the 4-wide path is covered too
+    fn cloud() -> Vec<Vec3> {
+        (0..11).map(|i| {
+            let t = i as f64;
+            Vec3::new(t, -2.0 * t, t * t * 0.5)
+        }).collect()
+    }
+
+    #[test]
+    fn test_add_all() {
+        let delta = Vec3::new(1.5, -0.25, 3.0);
+        let mut points = cloud();
+        add_all(&mut points, delta);
+
+        let mut expected = cloud();
+        for p in expected.iter_mut() {
+            *p += delta;
+        }
+        assert_eq!(points, expected);
+    }
+
+    #[test]
+    fn test_scale_all() {
+        let mut points = cloud();
+        scale_all(&mut points, -0.5);
+
+        let mut expected = cloud();
+        for p in expected.iter_mut() {
+            *p *= -0.5;
+        }
+        assert_eq!(points, expected);
+
+        let mut empty: Vec<Vec3> = Vec::new();
+        scale_all(&mut empty, 2.0);
+        assert!(empty.is_empty());
+    }
+}
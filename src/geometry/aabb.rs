@@ -0,0 +1,228 @@
+use std::cmp::{PartialEq};
+use crate::vector::vector3::Vec3;
+use crate::math::total_cmp_f64;
+
+// Axis aligned bounding box, min must be lower or equal to max on every axis
+#[derive(Clone, Copy, Debug)]
+pub struct Aabb {
+    pub min: Vec3,
+    pub max: Vec3
+}
+
+impl PartialEq for Aabb {
+    fn eq(&self, other: &Aabb) -> bool {
+        self.min == other.min && self.max == other.max
+    }
+}
+
+impl Aabb {
+    pub fn new(min: Vec3, max: Vec3) -> Aabb {
+        Aabb { min, max }
+    }
+
+    // Smallest box holding every point, None for an empty slice
+    pub fn from_points(points: &[Vec3]) -> Option<Aabb> {
+        let first = points.first()?;
+        Some(points.iter().fold(Aabb::new(*first, *first), |b, p| {
+            Aabb::new(b.min.min(p), b.max.max(p))
+        }))
+    }
+
+    // Points on the boundary are inside
+    pub fn contains(&self, p: &Vec3) -> bool {
+        p.x >= self.min.x && p.x <= self.max.x
+            && p.y >= self.min.y && p.y <= self.max.y
+            && p.z >= self.min.z && p.z <= self.max.z
+    }
+
+    // Touching boxes intersect (unlike penetration)
+    pub fn intersects(&self, other: &Aabb) -> bool {
+        self.min.x <= other.max.x && self.max.x >= other.min.x
+            && self.min.y <= other.max.y && self.max.y >= other.min.y
+            && self.min.z <= other.max.z && self.max.z >= other.min.z
+    }
+
+    // Smallest box holding both boxes
+    pub fn merge(&self, other: &Aabb) -> Aabb {
+        Aabb::new(self.min.min(&other.min), self.max.max(&other.max))
+    }
+
+    pub fn center(&self) -> Vec3 {
+        (self.min + self.max) * 0.5
+    }
+
+    // Half size on every axis
+    pub fn extents(&self) -> Vec3 {
+        (self.max - self.min) * 0.5
+    }
+
+    // Minimum translation vector: the smallest move to apply to self
+    // so that it stops overlapping other (the axis with the smallest overlap)
+    // Returns None if the boxes do not overlap, touching is not overlapping
+    pub fn penetration(&self, other: &Aabb) -> Option<Vec3> {
+        let axes = [
+            (self.min.x, self.max.x, other.min.x, other.max.x),
+            (self.min.y, self.max.y, other.min.y, other.max.y),
+            (self.min.z, self.max.z, other.min.z, other.max.z)
+        ];
+        let mut best_axis = 0;
+        let mut best_depth = f64::INFINITY;
+
+        for (axis, &(min, max, other_min, other_max)) in axes.iter().enumerate() {
+            let depth = max.min(other_max) - min.max(other_min);
+            if depth <= 0.0 {
+                return None;
+            }
+            if depth < best_depth {
+                best_axis = axis;
+                best_depth = depth;
+            }
+        }
+
+        // Push self away on the side of its own center
+        let (min, max, other_min, other_max) = axes[best_axis];
+        let depth = if min + max < other_min + other_max {
+            -best_depth
+        } else {
+            best_depth
+        };
+        Some(match best_axis {
+            0 => Vec3::new(depth, 0.0, 0.0),
+            1 => Vec3::new(0.0, depth, 0.0),
+            _ => Vec3::new(0.0, 0.0, depth)
+        })
+    }
+}
+
+// Broad phase: index pairs (i < j) of intersecting boxes
+// Boxes are sorted by their min along x, then each box is only tested
+// against the following ones until their min is past its max
+pub fn sweep_and_prune(boxes: &[Aabb]) -> Vec<(usize, usize)> {
+    let axis = Vec3::new(1.0, 0.0, 0.0);
+    let mut order: Vec<usize> = (0..boxes.len()).collect();
+    order.sort_by(|&a, &b| total_cmp_f64(&boxes[a].min.key_along(axis), &boxes[b].min.key_along(axis)));
+
+    let mut pairs = Vec::new();
+    for (k, &i) in order.iter().enumerate() {
+        let end = boxes[i].max.key_along(axis);
+        for &j in order[k + 1..].iter() {
+            if boxes[j].min.key_along(axis) > end {
+                break;
+            }
+            if boxes[i].intersects(&boxes[j]) {
+                pairs.push((i.min(j), i.max(j)));
+            }
+        }
+    }
+    pairs.sort_unstable();
+    pairs
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Aabb, sweep_and_prune};
+    use crate::vector::vector3::Vec3;
+
+    fn unit_box(offset: Vec3) -> Aabb {
+        Aabb::new(offset, offset + Vec3::new(1.0, 1.0, 1.0))
+    }
+
+    #[test]
+    fn test_penetration_x() {
+        let a = unit_box(Vec3::new(0.0, 0.0, 0.0));
+        let b = unit_box(Vec3::new(0.9, 0.2, -0.1));
+        let mtv = a.penetration(&b).unwrap();
+        assert_approx_eq::assert_approx_eq!(mtv.x, -0.1, 0.000001);
+        assert_eq!(mtv.y, 0.0);
+        assert_eq!(mtv.z, 0.0);
+
+        let mtv = b.penetration(&a).unwrap();
+        assert_approx_eq::assert_approx_eq!(mtv.x, 0.1, 0.000001);
+    }
+
+    #[test]
+    fn test_penetration_resolves() {
+        let a = unit_box(Vec3::new(0.0, 0.0, 0.0));
+        let b = unit_box(Vec3::new(0.5, 0.7, 0.4));
+        let mtv = a.penetration(&b).unwrap();
+        assert_approx_eq::assert_approx_eq!(mtv.y, -0.3, 0.000001);
+
+        let moved = Aabb::new(a.min + mtv, a.max + mtv);
+        assert!(moved.penetration(&b).is_none());
+    }
+
+    #[test]
+    fn test_penetration_none() {
+        let a = unit_box(Vec3::new(0.0, 0.0, 0.0));
+        assert!(a.penetration(&unit_box(Vec3::new(2.0, 0.0, 0.0))).is_none());
+        assert!(a.penetration(&unit_box(Vec3::new(1.0, 0.0, 0.0))).is_none());
+        assert!(a.penetration(&unit_box(Vec3::new(0.5, 0.5, -3.0))).is_none());
+    }
+
+    #[test]
+    fn test_from_points() {
+        let points = [
+            Vec3::new(1.0, -2.0, 3.0),
+            Vec3::new(-1.0, 4.0, 0.0),
+            Vec3::new(0.5, 0.0, 5.0)
+        ];
+        let b = Aabb::from_points(&points).unwrap();
+        assert_eq!(b.min, Vec3::new(-1.0, -2.0, 0.0));
+        assert_eq!(b.max, Vec3::new(1.0, 4.0, 5.0));
+        assert_eq!(Aabb::from_points(&[]), None);
+    }
+
+    #[test]
+    fn test_contains() {
+        let b = unit_box(Vec3::new(0.0, 0.0, 0.0));
+        assert!(b.contains(&Vec3::new(0.5, 0.5, 0.5)));
+        assert!(b.contains(&Vec3::new(0.0, 0.0, 0.0)));
+        assert!(b.contains(&Vec3::new(1.0, 0.5, 1.0)));
+        assert!(!b.contains(&Vec3::new(1.0001, 0.5, 0.5)));
+        assert!(!b.contains(&Vec3::new(0.5, -0.1, 0.5)));
+    }
+
+    #[test]
+    fn test_intersects() {
+        let a = unit_box(Vec3::new(0.0, 0.0, 0.0));
+        assert!(a.intersects(&unit_box(Vec3::new(0.5, 0.5, 0.5))));
+        assert!(a.intersects(&unit_box(Vec3::new(1.0, 0.0, 0.0))));
+        assert!(!a.intersects(&unit_box(Vec3::new(2.0, 0.0, 0.0))));
+        assert!(!a.intersects(&unit_box(Vec3::new(0.5, 0.5, -1.5))));
+    }
+
+    #[test]
+    fn test_merge_center_extents() {
+        let a = unit_box(Vec3::new(0.0, 0.0, 0.0));
+        let m = a.merge(&unit_box(Vec3::new(2.0, -1.0, 0.0)));
+        assert_eq!(m, Aabb::new(Vec3::new(0.0, -1.0, 0.0), Vec3::new(3.0, 1.0, 1.0)));
+        assert_eq!(m.center(), Vec3::new(1.5, 0.0, 0.5));
+        assert_eq!(m.extents(), Vec3::new(1.5, 1.0, 0.5));
+    }
+
+    #[test]
+    fn test_sweep_and_prune() {
+        let boxes = [
+            unit_box(Vec3::new(0.0, 0.0, 0.0)),
+            unit_box(Vec3::new(5.0, 0.0, 0.0)),
+            unit_box(Vec3::new(0.5, 0.5, 0.5)),
+            // Overlaps on x but not on y
+            unit_box(Vec3::new(0.2, 3.0, 0.0)),
+            unit_box(Vec3::new(5.5, 0.9, -0.5)),
+            Aabb::new(Vec3::new(-10.0, -0.5, 0.2), Vec3::new(0.1, 0.1, 0.3))
+        ];
+        let pairs = sweep_and_prune(&boxes);
+
+        let mut naive = Vec::new();
+        for i in 0..boxes.len() {
+            for j in i + 1..boxes.len() {
+                if boxes[i].intersects(&boxes[j]) {
+                    naive.push((i, j));
+                }
+            }
+        }
+        assert_eq!(pairs, naive);
+        assert_eq!(pairs, vec![(0, 2), (0, 5), (1, 4)]);
+        assert!(sweep_and_prune(&[]).is_empty());
+    }
+}
@@ -0,0 +1,82 @@
+use crate::vector::vector3::Vec3;
+
+// Bounding sphere of a point set using Ritter's algorithm
+// The result is not the minimal sphere but is usually within a few percent of it
+// Returns (center, radius) or None if there is no point
+pub fn bounding_sphere(points: &[Vec3]) -> Option<(Vec3, f64)> {
+    let first = *points.first()?;
+
+    // Approximate the diameter with two passes of "farthest point from"
+    let a = farthest_from(points, &first);
+    let b = farthest_from(points, &a);
+
+    let mut center = (a + b) * 0.5;
+    let mut radius = (b - a).magnitude() * 0.5;
+
+    // Grow the sphere to include every point left outside
+    for p in points {
+        let d = (*p - center).magnitude();
+        if d > radius {
+            let new_radius = (radius + d) * 0.5;
+            center += (*p - center) * ((new_radius - radius) / d);
+            radius = new_radius;
+        }
+    }
+    Some((center, radius))
+}
+
+fn farthest_from(points: &[Vec3], from: &Vec3) -> Vec3 {
+    let mut best = *from;
+    let mut best_dist = 0.0;
+
+    for p in points {
+        let d = (*p - *from).magnitude();
+        if d > best_dist {
+            best = *p;
+            best_dist = d;
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod test {
+    use super::bounding_sphere;
+    use crate::vector::vector3::Vec3;
+
+    #[test]
+    fn test_bounding_sphere_empty() {
+        assert!(bounding_sphere(&[]).is_none());
+    }
+
+    #[test]
+    fn test_bounding_sphere_single() {
+        let p = Vec3::new(1.0, 2.0, 3.0);
+        assert_eq!(bounding_sphere(&[p]), Some((p, 0.0)));
+    }
+
+    #[test]
+    fn test_bounding_sphere_contains_points() {
+        let points = [
+            Vec3::new(1.0, 1.0, 1.0),
+            Vec3::new(-1.0, 1.0, 1.0),
+            Vec3::new(1.0, -1.0, 1.0),
+            Vec3::new(1.0, 1.0, -1.0),
+            Vec3::new(-1.0, -1.0, 1.0),
+            Vec3::new(-1.0, 1.0, -1.0),
+            Vec3::new(1.0, -1.0, -1.0),
+            Vec3::new(-1.0, -1.0, -1.0),
+            Vec3::new(0.2, 0.5, -0.3),
+            Vec3::new(0.0, 0.0, 0.0)
+        ];
+        let (center, radius) = bounding_sphere(&points).unwrap();
+
+        for p in points.iter() {
+            assert!((*p - center).magnitude() <= radius + 0.000001);
+        }
+        // The minimal sphere of a cube of side 2 has a radius of sqrt(3)
+        let minimum = 3.0_f64.sqrt();
+        assert!(radius >= minimum - 0.000001);
+        assert!(radius <= minimum * 1.2);
+    }
+}
@@ -0,0 +1,128 @@
+// Intersections between lines and planes
+// Lines are a point and a direction (any length, both ways infinite)
+// Planes are Vec4 (a, b, c, d) for the points p with (a, b, c) . p + d = 0
+use crate::vector::vector3::Vec3;
+use crate::vector::vector4::Vec4;
+use super::ray::Ray;
+
+// Below this the sine of the angle between two directions counts as parallel
+pub const EPSILON: f64 = 1e-9;
+
+// Point where the line crosses the plane, None if they are parallel
+pub fn line_plane(point: Vec3, dir: Vec3, plane: Vec4) -> Option<Vec3> {
+    let normal = plane.to_pure_vec3();
+    let denom = normal.dot(&dir);
+    if denom.abs() <= EPSILON * normal.magnitude() * dir.magnitude() {
+        return None;
+    }
+    let t = -(normal.dot(&point) + plane.w) / denom;
+    Some(point + dir * t)
+}
+
+// Midpoint of the closest approach of two lines
+// None if they are parallel or further apart than `tolerance`
+pub fn line_line_3d(p1: Vec3, d1: Vec3, p2: Vec3, d2: Vec3, tolerance: f64) -> Option<Vec3> {
+    let n = d1.cross(&d2);
+    let n2 = n.dot(&n);
+    if n2 <= EPSILON * EPSILON * d1.dot(&d1) * d2.dot(&d2) {
+        return None;
+    }
+    let r = p2 - p1;
+    let t1 = r.cross(&d2).dot(&n) / n2;
+    let t2 = r.cross(&d1).dot(&n) / n2;
+    let c1 = p1 + d1 * t1;
+    let c2 = p2 + d2 * t2;
+    if (c2 - c1).magnitude() > tolerance {
+        return None;
+    }
+    Some((c1 + c2) * 0.5)
+}
+
+// Line shared by two planes, None if they are parallel
+// The ray starts at the point of the line closest to the origin
+pub fn plane_plane(p1: Vec4, p2: Vec4) -> Option<Ray> {
+    let n1 = p1.to_pure_vec3();
+    let n2 = p2.to_pure_vec3();
+    let dir = n1.cross(&n2);
+    let len2 = dir.dot(&dir);
+    if len2 <= EPSILON * EPSILON * n1.dot(&n1) * n2.dot(&n2) {
+        return None;
+    }
+    let point = (n2.cross(&dir) * -p1.w + dir.cross(&n1) * -p2.w) * (1.0 / len2);
+    Some(Ray::new(point, dir))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{line_plane, line_line_3d, plane_plane};
+    use crate::vector::vector3::Vec3;
+    use crate::vector::vector4::Vec4;
+
+    fn assert_vec3_eq(a: Vec3, b: Vec3) {
+        assert_approx_eq::assert_approx_eq!(a.x, b.x, 0.000001);
+        assert_approx_eq::assert_approx_eq!(a.y, b.y, 0.000001);
+        assert_approx_eq::assert_approx_eq!(a.z, b.z, 0.000001);
+    }
+
+    #[test]
+    fn test_line_plane() {
+        // z = 2
+        let plane = Vec4::new(0.0, 0.0, 1.0, -2.0);
+        let p = line_plane(Vec3::new(1.0, 1.0, 0.0), Vec3::new(1.0, 0.0, 1.0), plane).unwrap();
+        assert_vec3_eq(p, Vec3::new(3.0, 1.0, 2.0));
+
+        // Behind the point still counts, it is a line
+        let p = line_plane(Vec3::new(0.0, 0.0, 5.0), Vec3::new(0.0, 0.0, 1.0), plane).unwrap();
+        assert_vec3_eq(p, Vec3::new(0.0, 0.0, 2.0));
+
+        assert_eq!(line_plane(Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 1.0, 0.0), plane), None);
+        // Lying inside the plane is parallel too
+        assert_eq!(line_plane(Vec3::new(0.0, 0.0, 2.0), Vec3::new(1.0, 0.0, 0.0), plane), None);
+        assert_eq!(line_plane(Vec3::new(0.0, 0.0, 0.0), Vec3::new(0.0, 0.0, 0.0), plane), None);
+    }
+
+    #[test]
+    fn test_line_line_3d() {
+        let p = line_line_3d(
+            Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0),
+            Vec3::new(2.0, -1.0, 0.0), Vec3::new(0.0, 3.0, 0.0),
+            0.000001
+        ).unwrap();
+        assert_vec3_eq(p, Vec3::new(2.0, 0.0, 0.0));
+
+        // Skew lines one unit apart
+        let skew = |tolerance| line_line_3d(
+            Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0),
+            Vec3::new(0.0, 0.0, 1.0), Vec3::new(0.0, 1.0, 0.0),
+            tolerance
+        );
+        assert_eq!(skew(0.5), None);
+        assert_vec3_eq(skew(1.5).unwrap(), Vec3::new(0.0, 0.0, 0.5));
+
+        assert_eq!(line_line_3d(
+            Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 1.0, 0.0),
+            Vec3::new(0.0, 1.0, 0.0), Vec3::new(-2.0, -2.0, 0.0),
+            10.0
+        ), None);
+    }
+
+    #[test]
+    fn test_plane_plane() {
+        // x = 1 and y = 2 meet on a line along z
+        let r = plane_plane(Vec4::new(1.0, 0.0, 0.0, -1.0), Vec4::new(0.0, 2.0, 0.0, -4.0)).unwrap();
+        assert_vec3_eq(r.origin, Vec3::new(1.0, 2.0, 0.0));
+        assert_vec3_eq(r.dir, Vec3::new(0.0, 0.0, 1.0));
+
+        let a = Vec4::new(1.0, 1.0, 0.0, -1.0);
+        let b = Vec4::new(0.0, 1.0, 1.0, 3.0);
+        let r = plane_plane(a, b).unwrap();
+        for t in [-2.0, 0.0, 5.0].iter() {
+            let p = r.point_at(*t);
+            assert_approx_eq::assert_approx_eq!(a.to_pure_vec3().dot(&p) + a.w, 0.0, 0.000001);
+            assert_approx_eq::assert_approx_eq!(b.to_pure_vec3().dot(&p) + b.w, 0.0, 0.000001);
+        }
+
+        assert!(plane_plane(Vec4::new(0.0, 0.0, 1.0, 0.0), Vec4::new(0.0, 0.0, -2.0, 3.0)).is_none());
+        assert!(plane_plane(Vec4::new(0.0, 0.0, 1.0, 0.0), Vec4::new(0.0, 0.0, 1.0, 0.0)).is_none());
+    }
+}
@@ -0,0 +1,54 @@
+use crate::vector::vector3::Vec3;
+
+// Average normal of a closed triangle fan (cone / umbrella shaped)
+// Every triangle is (center, ring[i], ring[i + 1]), the last one wraps to ring[0]
+// The cross products are summed before normalizing, so bigger triangles weigh more
+// and slightly non-planar fans still give a stable normal
+// A counterclockwise ring gives a normal following the right-hand rule
+pub fn fan_normal(center: Vec3, ring: &[Vec3]) -> Vec3 {
+    let mut normal = Vec3::new(0.0, 0.0, 0.0);
+
+    for i in 0..ring.len() {
+        let e1 = ring[i] - center;
+        let e2 = ring[(i + 1) % ring.len()] - center;
+        normal += e1.cross(&e2);
+    }
+    normal.get_normalize()
+}
+
+#[cfg(test)]
+mod test {
+    use super::fan_normal;
+    use crate::vector::vector3::Vec3;
+
+    #[test]
+    fn test_fan_normal_flat() {
+        let ring = [
+            Vec3::new(1.0, 0.0, 0.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            Vec3::new(-1.0, 0.0, 0.0),
+            Vec3::new(0.0, -1.0, 0.0)
+        ];
+        let n = fan_normal(Vec3::new(0.0, 0.0, 0.0), &ring);
+        assert_eq!(n, Vec3::new(0.0, 0.0, 1.0));
+    }
+
+    #[test]
+    fn test_fan_normal_cone() {
+        let ring = [
+            Vec3::new(1.0, 0.0, 0.0),
+            Vec3::new(0.0, 1.0, 0.1),
+            Vec3::new(-1.0, 0.0, 0.0),
+            Vec3::new(0.0, -1.0, -0.1)
+        ];
+        let n = fan_normal(Vec3::new(0.0, 0.0, 1.0), &ring);
+        assert_approx_eq::assert_approx_eq!(n.magnitude(), 1.0, 0.000001);
+        assert!(n.z > 0.9);
+    }
+
+    #[test]
+    fn test_fan_normal_empty() {
+        let n = fan_normal(Vec3::new(0.0, 0.0, 0.0), &[]);
+        assert_eq!(n, Vec3::new(0.0, 0.0, 0.0));
+    }
+}
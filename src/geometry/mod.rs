@@ -0,0 +1,7 @@
+pub mod aabb;
+pub mod bounding;
+pub mod intersect;
+pub mod mesh;
+pub mod plane;
+pub mod ray;
+pub mod transform;
@@ -0,0 +1,81 @@
+use std::cmp::{PartialEq};
+use crate::vector::vector3::Vec3;
+
+// Points p with normal . p + d = 0, the normal is kept unit length
+// so signed distances are in world units
+#[derive(Clone, Copy, Debug)]
+pub struct Plane {
+    pub normal: Vec3,
+    pub d: f64
+}
+
+impl PartialEq for Plane {
+    fn eq(&self, other: &Plane) -> bool {
+        self.normal == other.normal && self.d == other.d
+    }
+}
+
+impl Plane {
+    // d is divided by the length of the normal as well
+    pub fn new(normal: Vec3, d: f64) -> Plane {
+        let magnitude = normal.magnitude();
+        if magnitude > 0.0 {
+            Plane { normal: normal * (1.0 / magnitude), d: d / magnitude }
+        } else {
+            Plane { normal, d }
+        }
+    }
+
+    pub fn from_point_normal(point: Vec3, normal: Vec3) -> Plane {
+        let normal = normal.get_normalize();
+        Plane { normal, d: -normal.dot(&point) }
+    }
+
+    // Positive on the side the normal points to
+    pub fn signed_distance(&self, p: &Vec3) -> f64 {
+        self.normal.dot(p) + self.d
+    }
+
+    // Orthogonal projection of p onto the plane
+    pub fn project_point(&self, p: &Vec3) -> Vec3 {
+        *p - self.normal * self.signed_distance(p)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Plane;
+    use crate::vector::vector3::Vec3;
+
+    #[test]
+    fn test_new_normalizes() {
+        let p = Plane::new(Vec3::new(0.0, 2.0, 0.0), -4.0);
+        assert_eq!(p.normal, Vec3::new(0.0, 1.0, 0.0));
+        assert_eq!(p.d, -2.0);
+        assert_eq!(p.signed_distance(&Vec3::new(5.0, 2.0, -1.0)), 0.0);
+    }
+
+    #[test]
+    fn test_signed_distance() {
+        let p = Plane::from_point_normal(Vec3::new(1.0, 1.0, 1.0), Vec3::new(0.0, 0.0, 3.0));
+        assert_eq!(p.normal, Vec3::new(0.0, 0.0, 1.0));
+        assert_eq!(p.signed_distance(&Vec3::new(1.0, 1.0, 1.0)), 0.0);
+        assert_eq!(p.signed_distance(&Vec3::new(-7.0, 3.0, 1.0)), 0.0);
+
+        let above = p.signed_distance(&Vec3::new(0.0, 0.0, 3.0));
+        let below = p.signed_distance(&Vec3::new(0.0, 0.0, -1.0));
+        assert_eq!(above, 2.0);
+        assert_eq!(below, -2.0);
+    }
+
+    #[test]
+    fn test_project_point() {
+        let n = Vec3::new(1.0, 1.0, 0.0);
+        let p = Plane::from_point_normal(Vec3::new(1.0, 0.0, 0.0), n);
+        let q = p.project_point(&Vec3::new(3.0, 2.0, 5.0));
+        assert_approx_eq::assert_approx_eq!(p.signed_distance(&q), 0.0, 0.000001);
+        assert_approx_eq::assert_approx_eq!(q.x, 1.0, 0.000001);
+        assert_approx_eq::assert_approx_eq!(q.y, 0.0, 0.000001);
+        assert_approx_eq::assert_approx_eq!(q.z, 5.0, 0.000001);
+    }
+}
@@ -0,0 +1,171 @@
+use std::cmp::{PartialEq};
+use crate::vector::vector3::Vec3;
+
+// Half line starting at origin, dir is always unit length
+#[derive(Clone, Copy, Debug)]
+pub struct Ray {
+    pub origin: Vec3,
+    pub dir: Vec3
+}
+
+impl PartialEq for Ray {
+    fn eq(&self, other: &Ray) -> bool {
+        self.origin == other.origin && self.dir == other.dir
+    }
+}
+
+impl Ray {
+    // Distance secondary rays are pushed off a surface to avoid
+    // hitting it again because of rounding errors (shadow acne)
+    pub const EPSILON: f64 = 1e-4;
+
+    pub fn new(origin: Vec3, dir: Vec3) -> Ray {
+        Ray { origin, dir: dir.get_normalize() }
+    }
+
+    pub fn point_at(&self, t: f64) -> Vec3 {
+        self.origin + self.dir * t
+    }
+
+    // Point of the ray nearest to p, points behind the origin map to it
+    pub fn closest_point(&self, p: &Vec3) -> Vec3 {
+        self.point_at((*p - self.origin).dot(&self.dir).max(0.0))
+    }
+
+    // Nearest positive hit parameter, the far hit when starting inside
+    pub fn intersect_sphere(&self, center: Vec3, radius: f64) -> Option<f64> {
+        let oc = self.origin - center;
+        let b = oc.dot(&self.dir);
+        let c = oc.dot(&oc) - radius * radius;
+        let discriminant = b * b - c;
+        if discriminant < 0.0 {
+            return None;
+        }
+        let root = discriminant.sqrt();
+        if -b - root > 0.0 {
+            Some(-b - root)
+        } else if -b + root > 0.0 {
+            Some(-b + root)
+        } else {
+            None
+        }
+    }
+
+    // Mirror ray leaving `hit_point`, the origin is nudged off the surface
+    // on the side the ray came from
+    pub fn reflect(&self, hit_point: Vec3, normal: Vec3) -> Ray {
+        let n = facing(normal.get_normalize(), &self.dir);
+        Ray::new(hit_point + n * Ray::EPSILON, self.dir.reflect(&n))
+    }
+
+    // Transmitted ray through a dielectric, eta is n_incident / n_transmitted
+    // The origin is nudged below the surface, None on total internal reflection
+    pub fn refract(&self, hit_point: Vec3, normal: Vec3, eta: f64) -> Option<Ray> {
+        let n = facing(normal.get_normalize(), &self.dir);
+        let dir = self.dir.refract(&n, eta)?;
+        Some(Ray::new(hit_point - n * Ray::EPSILON, dir))
+    }
+}
+
+// Flips the normal so it faces against dir
+fn facing(normal: Vec3, dir: &Vec3) -> Vec3 {
+    if normal.dot(dir) > 0.0 {
+        -normal
+    } else {
+        normal
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Ray;
+    use crate::vector::vector3::Vec3;
+
+    #[test]
+    fn test_new_normalizes() {
+        let r = Ray::new(Vec3::new(1.0, 2.0, 3.0), Vec3::new(0.0, 0.0, 5.0));
+        assert_eq!(r.dir, Vec3::new(0.0, 0.0, 1.0));
+        assert_eq!(r.origin, Vec3::new(1.0, 2.0, 3.0));
+    }
+
+    #[test]
+    fn test_reflect() {
+        let r = Ray::new(Vec3::new(-1.0, 1.0, 0.0), Vec3::new(1.0, -1.0, 0.0));
+        let hit = Vec3::new(0.0, 0.0, 0.0);
+        let reflected = r.reflect(hit, Vec3::new(0.0, 1.0, 0.0));
+
+        assert!(reflected.origin.y > 0.0);
+        assert_approx_eq::assert_approx_eq!(reflected.origin.y, Ray::EPSILON, 0.0000001);
+        let h = 0.5_f64.sqrt();
+        assert_approx_eq::assert_approx_eq!(reflected.dir.x, h, 0.000001);
+        assert_approx_eq::assert_approx_eq!(reflected.dir.y, h, 0.000001);
+        assert_approx_eq::assert_approx_eq!(reflected.dir.z, 0.0, 0.000001);
+    }
+
+    #[test]
+    fn test_reflect_back_face() {
+        // Hitting the surface from below with a normal pointing up
+        let r = Ray::new(Vec3::new(0.0, -1.0, 0.0), Vec3::new(0.0, 1.0, 0.0));
+        let reflected = r.reflect(Vec3::new(0.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0));
+        assert!(reflected.origin.y < 0.0);
+        assert_eq!(reflected.dir, Vec3::new(0.0, -1.0, 0.0));
+    }
+
+    #[test]
+    fn test_refract_into_denser() {
+        let incoming = Vec3::new(1.0, -1.0, 0.0);
+        let r = Ray::new(Vec3::new(-1.0, 1.0, 0.0), incoming);
+        let refracted = r.refract(Vec3::new(0.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0), 1.0 / 1.5).unwrap();
+
+        assert!(refracted.origin.y < 0.0);
+        assert_approx_eq::assert_approx_eq!(refracted.dir.magnitude(), 1.0, 0.000001);
+        assert!(refracted.dir.y < 0.0);
+        // Bends toward the normal: smaller angle with -n than the incoming ray
+        let down = Vec3::new(0.0, -1.0, 0.0);
+        assert!(refracted.dir.dot(&down) > r.dir.dot(&down));
+        // Snell: sin_t = eta * sin_i
+        assert_approx_eq::assert_approx_eq!(refracted.dir.x, 0.5_f64.sqrt() / 1.5, 0.000001);
+    }
+
+    #[test]
+    fn test_refract_total_internal_reflection() {
+        // Leaving glass at 60 degrees, past the ~41.8 degree critical angle
+        let angle = 60.0_f64.to_radians();
+        let r = Ray::new(Vec3::new(0.0, 0.0, 0.0), Vec3::new(angle.sin(), angle.cos(), 0.0));
+        assert!(r.refract(Vec3::new(0.0, 1.0, 0.0), Vec3::new(0.0, -1.0, 0.0), 1.5).is_none());
+
+        // Below the critical angle it still goes through
+        let angle = 30.0_f64.to_radians();
+        let r = Ray::new(Vec3::new(0.0, 0.0, 0.0), Vec3::new(angle.sin(), angle.cos(), 0.0));
+        assert!(r.refract(Vec3::new(0.0, 1.0, 0.0), Vec3::new(0.0, -1.0, 0.0), 1.5).is_some());
+    }
+
+    #[test]
+    fn test_point_at() {
+        let r = Ray::new(Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 2.0, 0.0));
+        assert_eq!(r.point_at(3.0), Vec3::new(1.0, 3.0, 0.0));
+    }
+
+    #[test]
+    fn test_closest_point() {
+        let r = Ray::new(Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0));
+        assert_eq!(r.closest_point(&Vec3::new(4.0, 2.0, -1.0)), Vec3::new(4.0, 0.0, 0.0));
+        assert_eq!(r.closest_point(&Vec3::new(-4.0, 2.0, 0.0)), Vec3::new(0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_intersect_sphere() {
+        let r = Ray::new(Vec3::new(0.0, 0.0, -5.0), Vec3::new(0.0, 0.0, 1.0));
+        let t = r.intersect_sphere(Vec3::new(0.0, 0.0, 0.0), 1.0).unwrap();
+        assert_approx_eq::assert_approx_eq!(t, 4.0, 0.000001);
+
+        // From inside the far side is hit
+        let inside = Ray::new(Vec3::new(0.0, 0.0, 0.0), Vec3::new(0.0, 0.0, 1.0));
+        let t = inside.intersect_sphere(Vec3::new(0.0, 0.0, 0.0), 2.0).unwrap();
+        assert_approx_eq::assert_approx_eq!(t, 2.0, 0.000001);
+
+        assert_eq!(r.intersect_sphere(Vec3::new(0.0, 3.0, 0.0), 1.0), None);
+        // Sphere behind the origin
+        assert_eq!(r.intersect_sphere(Vec3::new(0.0, 0.0, -10.0), 1.0), None);
+    }
+}
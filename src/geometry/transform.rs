@@ -0,0 +1,119 @@
+use std::cmp::{PartialEq};
+use crate::vector::vector3::Vec3;
+use crate::vector::quaternions::Quaternion;
+use crate::matrix::matrix4::Mat4;
+
+// Translation, rotation and scale applied as T * R * S
+#[derive(Clone, Copy, Debug)]
+pub struct Transform {
+    pub translation: Vec3,
+    pub rotation: Quaternion,
+    pub scale: Vec3
+}
+
+impl PartialEq for Transform {
+    fn eq(&self, other: &Transform) -> bool {
+        self.translation == other.translation
+            && self.rotation == other.rotation
+            && self.scale == other.scale
+    }
+}
+
+impl Transform {
+    pub fn new(translation: Vec3, rotation: Quaternion, scale: Vec3) -> Transform {
+        Transform { translation, rotation, scale }
+    }
+
+    pub fn identity() -> Transform {
+        Transform::new(
+            Vec3::new(0.0, 0.0, 0.0),
+            Quaternion::identity(),
+            Vec3::new(1.0, 1.0, 1.0)
+        )
+    }
+
+    pub fn to_mat4(&self) -> Mat4 {
+        Mat4::from_trs(self.translation, self.rotation, self.scale)
+    }
+
+    // Linear blend skinning: translations and scales are blended linearly,
+    // rotations with a weighted quaternion average
+    // Weights are divided by their sum, extra transforms or weights are ignored
+    // An empty input or a zero total weight gives the identity
+    pub fn blend_weighted(transforms: &[Transform], weights: &[f64]) -> Transform {
+        let total: f64 = weights.iter().take(transforms.len()).sum();
+        if total == 0.0 {
+            return Transform::identity();
+        }
+
+        let mut translation = Vec3::new(0.0, 0.0, 0.0);
+        let mut scale = Vec3::new(0.0, 0.0, 0.0);
+        for (t, &w) in transforms.iter().zip(weights.iter()) {
+            translation += t.translation * (w / total);
+            scale += t.scale * (w / total);
+        }
+        let rotations: Vec<Quaternion> = transforms.iter().map(|t| t.rotation).collect();
+        let rotation = Quaternion::weighted_average(&rotations, weights);
+        Transform::new(translation, rotation, scale)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Transform;
+    use crate::vector::vector3::Vec3;
+    use crate::vector::vector4::Vec4;
+    use crate::vector::quaternions::Quaternion;
+
+    fn assert_vec3_eq(a: Vec3, b: Vec3) {
+        assert_approx_eq::assert_approx_eq!(a.x, b.x, 0.000001);
+        assert_approx_eq::assert_approx_eq!(a.y, b.y, 0.000001);
+        assert_approx_eq::assert_approx_eq!(a.z, b.z, 0.000001);
+    }
+
+    #[test]
+    fn test_to_mat4() {
+        let t = Transform::new(
+            Vec3::new(1.0, 2.0, 3.0),
+            Quaternion::from_axis_angle(Vec3::new(0.0, 0.0, 1.0), std::f64::consts::PI / 2.0),
+            Vec3::new(2.0, 2.0, 2.0)
+        );
+        let p = t.to_mat4() * Vec4::new(1.0, 0.0, 0.0, 1.0);
+        assert_vec3_eq(p.to_pure_vec3(), Vec3::new(1.0, 4.0, 3.0));
+        assert_eq!(Transform::identity().to_mat4(), crate::matrix::matrix4::Mat4::identity());
+    }
+
+    #[test]
+    fn test_blend_weighted_identical() {
+        let t = Transform::new(
+            Vec3::new(1.0, -2.0, 3.0),
+            Quaternion::from_axis_angle(Vec3::new(1.0, 1.0, 0.0), 0.8),
+            Vec3::new(1.0, 2.0, 0.5)
+        );
+        let r = Transform::blend_weighted(&[t, t], &[0.3, 0.7]);
+        assert_vec3_eq(r.translation, t.translation);
+        assert_vec3_eq(r.scale, t.scale);
+        assert_vec3_eq(r.rotation.v, t.rotation.v);
+        assert_approx_eq::assert_approx_eq!(r.rotation.s, t.rotation.s, 0.000001);
+    }
+
+    #[test]
+    fn test_blend_weighted_between() {
+        let axis = Vec3::new(0.0, 1.0, 0.0);
+        let a = Transform::new(Vec3::new(0.0, 0.0, 0.0), Quaternion::from_axis_angle(axis, 0.0), Vec3::new(1.0, 1.0, 1.0));
+        let b = Transform::new(Vec3::new(2.0, 4.0, 0.0), Quaternion::from_axis_angle(axis, 1.0), Vec3::new(3.0, 1.0, 1.0));
+        let r = Transform::blend_weighted(&[a, b], &[0.5, 0.5]);
+        assert_vec3_eq(r.translation, Vec3::new(1.0, 2.0, 0.0));
+        assert_vec3_eq(r.scale, Vec3::new(2.0, 1.0, 1.0));
+        let (r_axis, angle) = r.rotation.to_axis_angle();
+        assert_vec3_eq(r_axis, axis);
+        assert_approx_eq::assert_approx_eq!(angle, 0.5, 0.000001);
+
+        // The sign of a quaternion does not change the blend
+        let flipped = Transform::new(b.translation, b.rotation * -1.0, b.scale);
+        let r2 = Transform::blend_weighted(&[a, flipped], &[0.5, 0.5]);
+        assert_approx_eq::assert_approx_eq!(r2.rotation.to_axis_angle().1, 0.5, 0.000001);
+
+        assert_eq!(Transform::blend_weighted(&[], &[]), Transform::identity());
+    }
+}
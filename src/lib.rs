@@ -1 +1,8 @@
-pub mod vector;
\ No newline at end of file
+pub mod math;
+pub mod vector;
+pub mod matrix;
+pub mod geometry;
+pub mod physics;
+pub mod shading;
+pub mod batch;
+pub mod prelude;
@@ -0,0 +1,183 @@
+// Scalar helpers shared by the vector and matrix types
+use std::f64::consts::PI;
+use std::cmp::Ordering;
+
+// Wraps an angle in radians into [-PI, PI)
+pub fn wrap_angle(angle: f64) -> f64 {
+    (angle + PI).rem_euclid(2.0 * PI) - PI
+}
+
+// Radians to degrees
+pub fn degrees(radians: f64) -> f64 {
+    radians * 180.0 / PI
+}
+
+// Degrees to radians
+pub fn radians(degrees: f64) -> f64 {
+    degrees * PI / 180.0
+}
+
+// Dot product of two slices of any length
+// Extra values of the longest slice are ignored
+pub fn dot_slice(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b.iter()).map(|(x, y)| x * y).sum()
+}
+
+// Euclidean distance of two slices of any length
+// Extra values of the longest slice are ignored
+pub fn distance_slice(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b.iter()).map(|(x, y)| (x - y) * (x - y)).sum::<f64>().sqrt()
+}
+
+// Total order on f64 (IEEE 754 totalOrder), NaN never panics a sort
+// -NaN < -inf < ... < -0.0 < 0.0 < ... < inf < NaN
+// Signature fits sort_by directly: `values.sort_by(total_cmp_f64)`
+pub fn total_cmp_f64(a: &f64, b: &f64) -> Ordering {
+    a.total_cmp(b)
+}
+
+// Hermite interpolation between 0 and 1 as x goes from edge0 to edge1
+// x is clamped, the first derivative is 0 at both edges
+pub fn smoothstep(edge0: f64, edge1: f64, x: f64) -> f64 {
+    let t = ((x - edge0) / (edge1 - edge0)).clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
+
+// Like smoothstep with first and second derivatives at 0 on both edges
+pub fn smootherstep(edge0: f64, edge1: f64, x: f64) -> f64 {
+    let t = ((x - edge0) / (edge1 - edge0)).clamp(0.0, 1.0);
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+// Easing curves remapping an interpolation factor in [0, 1]
+// Every curve maps 0 to 0 and 1 to 1
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Ease {
+    Linear,
+    QuadIn,
+    QuadOut,
+    QuadInOut,
+    CubicIn,
+    CubicOut,
+    CubicInOut,
+    SineInOut
+}
+
+impl Ease {
+    pub fn apply(&self, t: f64) -> f64 {
+        match self {
+            Ease::Linear => t,
+            Ease::QuadIn => t * t,
+            Ease::QuadOut => t * (2.0 - t),
+            Ease::QuadInOut => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    1.0 - 2.0 * (1.0 - t) * (1.0 - t)
+                }
+            }
+            Ease::CubicIn => t * t * t,
+            Ease::CubicOut => 1.0 - (1.0 - t).powi(3),
+            Ease::CubicInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - 4.0 * (1.0 - t).powi(3)
+                }
+            }
+            Ease::SineInOut => 0.5 - 0.5 * (PI * t).cos()
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{wrap_angle, degrees, radians, dot_slice, distance_slice, Ease, smoothstep, smootherstep, total_cmp_f64};
+    use crate::vector::vector3::Vec3;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_wrap_angle() {
+        assert_eq!(wrap_angle(0.0), 0.0);
+        assert_approx_eq::assert_approx_eq!(wrap_angle(PI / 2.0), PI / 2.0, 0.000001);
+        assert_approx_eq::assert_approx_eq!(wrap_angle(2.0 * PI + 0.5), 0.5, 0.000001);
+        assert_approx_eq::assert_approx_eq!(wrap_angle(-2.0 * PI - 0.5), -0.5, 0.000001);
+        assert_approx_eq::assert_approx_eq!(wrap_angle(3.0 * PI / 2.0), -PI / 2.0, 0.000001);
+        assert_approx_eq::assert_approx_eq!(wrap_angle(3.0 * PI).abs(), PI, 0.000001);
+    }
+
+    #[test]
+    fn test_dot_slice() {
+        let a = Vec3::new(1.0, 2.0, 3.0);
+        let b = Vec3::new(-4.0, 5.0, 0.5);
+        assert_eq!(dot_slice(&[1.0, 2.0, 3.0], &[-4.0, 5.0, 0.5]), a.dot(&b));
+        assert_eq!(dot_slice(&[1.0, 2.0, 3.0, 4.0, 5.0], &[1.0, 1.0, 1.0, 1.0]), 10.0);
+        assert_eq!(dot_slice(&[], &[1.0]), 0.0);
+    }
+
+    #[test]
+    fn test_distance_slice() {
+        let a = Vec3::new(1.0, 2.0, 3.0);
+        let b = Vec3::new(-4.0, 5.0, 0.5);
+        assert_eq!(distance_slice(&[1.0, 2.0, 3.0], &[-4.0, 5.0, 0.5]), (a - b).magnitude());
+        assert_eq!(distance_slice(&[0.0, 0.0, 0.0, 0.0, 9.0], &[1.0, 1.0, 1.0, 1.0]), 2.0);
+    }
+
+    #[test]
+    fn test_ease_endpoints() {
+        let curves = [
+            Ease::Linear, Ease::QuadIn, Ease::QuadOut, Ease::QuadInOut,
+            Ease::CubicIn, Ease::CubicOut, Ease::CubicInOut, Ease::SineInOut
+        ];
+        for e in curves.iter() {
+            assert_eq!(e.apply(0.0), 0.0);
+            assert_approx_eq::assert_approx_eq!(e.apply(1.0), 1.0, 0.000001);
+        }
+        assert!(Ease::QuadIn.apply(0.25) < 0.25);
+        assert!(Ease::CubicIn.apply(0.25) < Ease::QuadIn.apply(0.25));
+        assert!(Ease::QuadOut.apply(0.25) > 0.25);
+        assert_approx_eq::assert_approx_eq!(Ease::CubicInOut.apply(0.5), 0.5, 0.000001);
+    }
+
+    #[test]
+    fn test_smoothstep() {
+        assert_eq!(smoothstep(0.0, 1.0, 0.0), 0.0);
+        assert_eq!(smoothstep(0.0, 1.0, 1.0), 1.0);
+        assert_eq!(smoothstep(0.0, 1.0, 0.5), 0.5);
+        assert_eq!(smoothstep(2.0, 4.0, 1.0), 0.0);
+        assert_eq!(smoothstep(2.0, 4.0, 9.0), 1.0);
+        assert_approx_eq::assert_approx_eq!(smoothstep(0.0, 1.0, 0.25), 0.15625, 0.000001);
+    }
+
+    #[test]
+    fn test_smootherstep() {
+        assert_eq!(smootherstep(0.0, 1.0, 0.0), 0.0);
+        assert_eq!(smootherstep(0.0, 1.0, 1.0), 1.0);
+        assert_eq!(smootherstep(0.0, 1.0, 0.5), 0.5);
+        assert_eq!(smootherstep(-1.0, 1.0, -3.0), 0.0);
+        assert!(smootherstep(0.0, 1.0, 0.25) < smoothstep(0.0, 1.0, 0.25));
+    }
+
+    #[test]
+    fn test_total_cmp_f64() {
+        use std::cmp::Ordering;
+
+        assert_eq!(total_cmp_f64(&1.0, &2.0), Ordering::Less);
+        assert_eq!(total_cmp_f64(&-0.0, &0.0), Ordering::Less);
+        assert_eq!(total_cmp_f64(&f64::NAN, &f64::INFINITY), Ordering::Greater);
+
+        let mut values = [3.0, f64::NAN, -1.0, 0.5];
+        values.sort_by(total_cmp_f64);
+        assert_eq!(values[..3], [-1.0, 0.5, 3.0]);
+        assert!(values[3].is_nan());
+    }
+
+    #[test]
+    fn test_degrees_radians() {
+        assert_eq!(radians(180.0), PI);
+        assert_eq!(degrees(PI), 180.0);
+        assert_approx_eq::assert_approx_eq!(radians(90.0), PI / 2.0, 0.000001);
+        assert_approx_eq::assert_approx_eq!(degrees(-PI / 4.0), -45.0, 0.000001);
+        assert_approx_eq::assert_approx_eq!(degrees(radians(123.4)), 123.4, 0.000001);
+    }
+}
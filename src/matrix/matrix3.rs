@@ -0,0 +1,411 @@
+// Matrices are stored row-major: m[row][col]
+// Vectors are treated as columns, so M * v transforms v
+use std::ops::{
+    Mul
+};
+use std::cmp::{PartialEq};
+use crate::vector::vector3::Vec3;
+use crate::vector::quaternions::Quaternion;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Mat3 {
+    pub m: [[f64; 3]; 3]
+}
+
+impl std::fmt::Display for Mat3 {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Mat3(")?;
+        for (i, row) in self.m.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "[{:.2}, {:.2}, {:.2}]", row[0], row[1], row[2])?;
+        }
+        write!(f, ")")
+    }
+}
+
+impl PartialEq for Mat3 {
+    fn eq(&self, other: &Mat3) -> bool {
+        self.m == other.m
+    }
+}
+
+impl Mat3 {
+    pub fn new(m: [[f64; 3]; 3]) -> Mat3 {
+        Mat3 { m }
+    }
+
+    pub fn identity() -> Mat3 {
+        Mat3::from_diagonal(Vec3::new(1.0, 1.0, 1.0))
+    }
+
+    pub fn from_diagonal(v: Vec3) -> Mat3 {
+        Mat3::new([
+            [v.x, 0.0, 0.0],
+            [0.0, v.y, 0.0],
+            [0.0, 0.0, v.z]
+        ])
+    }
+
+    pub fn diagonal(&self) -> Vec3 {
+        Vec3::new(self.m[0][0], self.m[1][1], self.m[2][2])
+    }
+
+    // Rotation matrix of a unit quaternion
+    pub fn from_quaternion(q: Quaternion) -> Mat3 {
+        let (x, y, z, w) = (q.v.x, q.v.y, q.v.z, q.s);
+        Mat3::new([
+            [1.0 - 2.0 * (y * y + z * z), 2.0 * (x * y - z * w), 2.0 * (x * z + y * w)],
+            [2.0 * (x * y + z * w), 1.0 - 2.0 * (x * x + z * z), 2.0 * (y * z - x * w)],
+            [2.0 * (x * z - y * w), 2.0 * (y * z + x * w), 1.0 - 2.0 * (x * x + y * y)]
+        ])
+    }
+
+    // Unit quaternion of a rotation matrix (Shepperd's method)
+    // The branch on the largest diagonal term keeps the division well conditioned
+    pub fn to_quaternion(&self) -> Quaternion {
+        let m = &self.m;
+        let trace = m[0][0] + m[1][1] + m[2][2];
+        let (x, y, z, w) = if trace > 0.0 {
+            let s = (trace + 1.0).sqrt() * 2.0;
+            ((m[2][1] - m[1][2]) / s, (m[0][2] - m[2][0]) / s, (m[1][0] - m[0][1]) / s, 0.25 * s)
+        } else if m[0][0] > m[1][1] && m[0][0] > m[2][2] {
+            let s = (1.0 + m[0][0] - m[1][1] - m[2][2]).sqrt() * 2.0;
+            (0.25 * s, (m[0][1] + m[1][0]) / s, (m[0][2] + m[2][0]) / s, (m[2][1] - m[1][2]) / s)
+        } else if m[1][1] > m[2][2] {
+            let s = (1.0 + m[1][1] - m[0][0] - m[2][2]).sqrt() * 2.0;
+            ((m[0][1] + m[1][0]) / s, 0.25 * s, (m[1][2] + m[2][1]) / s, (m[0][2] - m[2][0]) / s)
+        } else {
+            let s = (1.0 + m[2][2] - m[0][0] - m[1][1]).sqrt() * 2.0;
+            ((m[0][2] + m[2][0]) / s, (m[1][2] + m[2][1]) / s, 0.25 * s, (m[1][0] - m[0][1]) / s)
+        };
+        let mut q = Quaternion::new(Vec3::new(x, y, z), w);
+        q.normalize();
+        q
+    }
+
+    pub fn transpose(&self) -> Mat3 {
+        let mut r = *self;
+        for i in 0..3 {
+            for j in 0..3 {
+                r.m[i][j] = self.m[j][i];
+            }
+        }
+        r
+    }
+
+    pub fn determinant(&self) -> f64 {
+        let m = &self.m;
+        m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+    }
+
+    // Adjugate divided by the determinant
+    // Returns None if the matrix is singular
+    pub fn inverse(&self) -> Option<Mat3> {
+        let det = self.determinant();
+        if self.is_singular(det) {
+            return None;
+        }
+        let m = &self.m;
+        let inv = 1.0 / det;
+        Some(Mat3::new([
+            [
+                (m[1][1] * m[2][2] - m[1][2] * m[2][1]) * inv,
+                (m[0][2] * m[2][1] - m[0][1] * m[2][2]) * inv,
+                (m[0][1] * m[1][2] - m[0][2] * m[1][1]) * inv
+            ],
+            [
+                (m[1][2] * m[2][0] - m[1][0] * m[2][2]) * inv,
+                (m[0][0] * m[2][2] - m[0][2] * m[2][0]) * inv,
+                (m[0][2] * m[1][0] - m[0][0] * m[1][2]) * inv
+            ],
+            [
+                (m[1][0] * m[2][1] - m[1][1] * m[2][0]) * inv,
+                (m[0][1] * m[2][0] - m[0][0] * m[2][1]) * inv,
+                (m[0][0] * m[1][1] - m[0][1] * m[1][0]) * inv
+            ]
+        ]))
+    }
+
+    // Solves M * x = b with Cramer's rule
+    // Returns None if the matrix is singular
+    pub fn solve(&self, b: Vec3) -> Option<Vec3> {
+        let det = self.determinant();
+        if self.is_singular(det) {
+            return None;
+        }
+        Some(Vec3::new(
+            self.with_column(0, b).determinant() / det,
+            self.with_column(1, b).determinant() / det,
+            self.with_column(2, b).determinant() / det
+        ))
+    }
+
+    // The determinant scales with the cube of the entries,
+    // so the cutoff is relative to the largest one
+    fn is_singular(&self, det: f64) -> bool {
+        let max = self.m.iter().flatten().fold(0.0f64, |a, b| a.max(b.abs()));
+        det.abs() <= f64::EPSILON * max.powi(3)
+    }
+
+    fn with_column(&self, col: usize, v: Vec3) -> Mat3 {
+        let mut r = *self;
+        r.m[0][col] = v.x;
+        r.m[1][col] = v.y;
+        r.m[2][col] = v.z;
+        r
+    }
+}
+
+impl Mul<Mat3> for Mat3 {
+    type Output = Mat3;
+
+    fn mul(self, other: Mat3) -> Mat3 {
+        let mut r = Mat3::new([[0.0; 3]; 3]);
+        for i in 0..3 {
+            for j in 0..3 {
+                r.m[i][j] = (0..3).map(|k| self.m[i][k] * other.m[k][j]).sum();
+            }
+        }
+        r
+    }
+}
+
+impl Mul<Vec3> for Mat3 {
+    type Output = Vec3;
+
+    fn mul(self, v: Vec3) -> Vec3 {
+        let m = &self.m;
+        Vec3::new(
+            m[0][0] * v.x + m[0][1] * v.y + m[0][2] * v.z,
+            m[1][0] * v.x + m[1][1] * v.y + m[1][2] * v.z,
+            m[2][0] * v.x + m[2][1] * v.y + m[2][2] * v.z
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Mat3;
+    use crate::vector::vector3::Vec3;
+    use crate::vector::quaternions::Quaternion;
+
+    fn assert_mat3_eq(a: Mat3, b: Mat3) {
+        for i in 0..3 {
+            for j in 0..3 {
+                assert_approx_eq::assert_approx_eq!(a.m[i][j], b.m[i][j], 0.000001);
+            }
+        }
+    }
+
+    fn assert_vec3_eq(a: Vec3, b: Vec3) {
+        assert_approx_eq::assert_approx_eq!(a.x, b.x, 0.000001);
+        assert_approx_eq::assert_approx_eq!(a.y, b.y, 0.000001);
+        assert_approx_eq::assert_approx_eq!(a.z, b.z, 0.000001);
+    }
+
+    #[test]
+    fn test_identity() {
+        let v = Vec3::new(1.0, 2.0, 3.0);
+        assert_eq!(Mat3::identity() * v, v);
+    }
+
+    #[test]
+    fn test_mul_vec3() {
+        let m = Mat3::new([
+            [1.0, 2.0, 3.0],
+            [4.0, 5.0, 6.0],
+            [7.0, 8.0, 9.0]
+        ]);
+        assert_eq!(m * Vec3::new(1.0, 0.0, -1.0), Vec3::new(-2.0, -2.0, -2.0));
+    }
+
+    #[test]
+    fn test_from_diagonal() {
+        let m = Mat3::from_diagonal(Vec3::new(2.0, 3.0, 4.0));
+        assert_eq!(m * Vec3::new(1.0, 0.0, 0.0), Vec3::new(2.0, 0.0, 0.0));
+        assert_eq!(m * Vec3::new(0.0, 1.0, 0.0), Vec3::new(0.0, 3.0, 0.0));
+        assert_eq!(m * Vec3::new(0.0, 0.0, 1.0), Vec3::new(0.0, 0.0, 4.0));
+        assert_eq!(m.diagonal(), Vec3::new(2.0, 3.0, 4.0));
+    }
+
+    #[test]
+    fn test_fmt() {
+        let m = Mat3::identity();
+        assert_eq!(
+            format!("{}", m),
+            "Mat3([1.00, 0.00, 0.00], [0.00, 1.00, 0.00], [0.00, 0.00, 1.00])"
+        );
+    }
+
+    #[test]
+    fn test_determinant() {
+        let m = Mat3::new([
+            [2.0, 0.0, 1.0],
+            [1.0, 3.0, 2.0],
+            [1.0, 1.0, 2.0]
+        ]);
+        assert_eq!(m.determinant(), 6.0);
+        assert_eq!(Mat3::from_diagonal(Vec3::new(2.0, 3.0, 4.0)).determinant(), 24.0);
+    }
+
+    #[test]
+    fn test_solve_identity() {
+        let b = Vec3::new(1.0, -2.0, 3.0);
+        assert_eq!(Mat3::identity().solve(b), Some(b));
+    }
+
+    #[test]
+    fn test_solve() {
+        // 2x + y - z = 8, -3x - y + 2z = -11, -2x + y + 2z = -3
+        let m = Mat3::new([
+            [2.0, 1.0, -1.0],
+            [-3.0, -1.0, 2.0],
+            [-2.0, 1.0, 2.0]
+        ]);
+        let x = m.solve(Vec3::new(8.0, -11.0, -3.0)).unwrap();
+        assert_approx_eq::assert_approx_eq!(x.x, 2.0, 0.000001);
+        assert_approx_eq::assert_approx_eq!(x.y, 3.0, 0.000001);
+        assert_approx_eq::assert_approx_eq!(x.z, -1.0, 0.000001);
+    }
+
+    #[test]
+    fn test_solve_singular() {
+        let m = Mat3::new([
+            [1.0, 2.0, 3.0],
+            [2.0, 4.0, 6.0],
+            [0.0, 1.0, 1.0]
+        ]);
+        assert!(m.solve(Vec3::new(1.0, 2.0, 3.0)).is_none());
+    }
+
+    #[test]
+    fn test_solve_small_scale() {
+        let m = Mat3::from_diagonal(Vec3::new(1e-6, 2e-6, 4e-6));
+        let x = m.solve(Vec3::new(1e-6, 1e-6, 1e-6)).unwrap();
+        assert_approx_eq::assert_approx_eq!(x.x, 1.0, 0.000001);
+        assert_approx_eq::assert_approx_eq!(x.y, 0.5, 0.000001);
+        assert_approx_eq::assert_approx_eq!(x.z, 0.25, 0.000001);
+    }
+
+    #[test]
+    fn test_mul_mat3() {
+        let a = Mat3::new([
+            [1.0, 2.0, 0.0],
+            [0.0, 1.0, 3.0],
+            [4.0, 0.0, 1.0]
+        ]);
+        let b = Mat3::from_diagonal(Vec3::new(2.0, 3.0, 4.0));
+        assert_eq!(a * Mat3::identity(), a);
+        assert_eq!(a * b, Mat3::new([
+            [2.0, 6.0, 0.0],
+            [0.0, 3.0, 12.0],
+            [8.0, 0.0, 4.0]
+        ]));
+        let v = Vec3::new(1.0, -1.0, 2.0);
+        assert_eq!((a * b) * v, a * (b * v));
+    }
+
+    #[test]
+    fn test_transpose() {
+        let a = Mat3::new([
+            [1.0, 2.0, 3.0],
+            [4.0, 5.0, 6.0],
+            [7.0, 8.0, 9.0]
+        ]);
+        assert_eq!(a.transpose(), Mat3::new([
+            [1.0, 4.0, 7.0],
+            [2.0, 5.0, 8.0],
+            [3.0, 6.0, 9.0]
+        ]));
+        assert_eq!(a.transpose().transpose(), a);
+    }
+
+    #[test]
+    fn test_from_quaternion_matches_rotate() {
+        let axes = [
+            Vec3::new(0.0, 0.0, 1.0),
+            Vec3::new(1.0, 0.0, 0.0),
+            Vec3::new(1.0, 2.0, -1.0)
+        ];
+        let points = [
+            Vec3::new(1.0, 0.0, 0.0),
+            Vec3::new(0.5, -2.0, 3.0)
+        ];
+        for axis in axes.iter() {
+            for degrees in [30.0, 90.0, 200.0].iter() {
+                let q = Quaternion::from_axis_angle(*axis, f64::to_radians(*degrees));
+                let m = Mat3::from_quaternion(q);
+                for p in points.iter() {
+                    let expected = Quaternion::new(*p, 0.0)
+                        .rotate(&Quaternion::new(*axis, *degrees));
+                    assert_vec3_eq(m * *p, expected);
+                }
+                assert_approx_eq::assert_approx_eq!(m.determinant(), 1.0, 0.000001);
+            }
+        }
+    }
+
+    #[test]
+    fn test_inverse() {
+        let m = Mat3::new([
+            [2.0, 1.0, -1.0],
+            [-3.0, -1.0, 2.0],
+            [-2.0, 1.0, 2.0]
+        ]);
+        let inv = m.inverse().unwrap();
+        assert_mat3_eq(m * inv, Mat3::identity());
+        assert_mat3_eq(inv * m, Mat3::identity());
+    }
+
+    #[test]
+    fn test_inverse_rotation() {
+        let q = Quaternion::from_axis_angle(Vec3::new(1.0, 1.0, 0.0), 0.7);
+        let m = Mat3::from_quaternion(q);
+        assert_mat3_eq(m.inverse().unwrap(), m.transpose());
+    }
+
+    #[test]
+    fn test_inverse_singular() {
+        let m = Mat3::new([
+            [1.0, 2.0, 3.0],
+            [2.0, 4.0, 6.0],
+            [0.0, 1.0, 1.0]
+        ]);
+        assert!(m.inverse().is_none());
+    }
+
+    #[test]
+    fn test_inverse_small_scale() {
+        let m = Mat3::new([
+            [2e-6, 1e-6, -1e-6],
+            [-3e-6, -1e-6, 2e-6],
+            [-2e-6, 1e-6, 2e-6]
+        ]);
+        let inv = m.inverse().unwrap();
+        assert_mat3_eq(m * inv, Mat3::identity());
+    }
+
+    #[test]
+    fn test_to_quaternion() {
+        let axes = [
+            Vec3::new(1.0, 0.0, 0.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            Vec3::new(0.0, 0.0, 1.0),
+            Vec3::new(1.0, -2.0, 0.5)
+        ];
+        for axis in axes.iter() {
+            for angle in [0.0, 0.5, 2.0, 3.1].iter() {
+                let q = Quaternion::from_axis_angle(*axis, *angle);
+                let r = Mat3::from_quaternion(q).to_quaternion();
+                // q and -q are the same rotation
+                let sign = if r.dot(&q) < 0.0 { -1.0 } else { 1.0 };
+                assert_vec3_eq(r.v * sign, q.v);
+                assert_approx_eq::assert_approx_eq!(r.s * sign, q.s, 0.000001);
+            }
+        }
+    }
+}
@@ -0,0 +1,729 @@
+// Matrices are stored row-major: m[row][col]
+// Vectors are treated as columns, so M * v transforms v
+// The translation of an affine matrix lives in the last column
+use std::ops::{
+    Mul
+};
+use std::cmp::{PartialEq};
+use crate::vector::vector3::Vec3;
+use crate::vector::vector4::Vec4;
+use crate::vector::quaternions::Quaternion;
+use super::matrix3::Mat3;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Mat4 {
+    pub m: [[f64; 4]; 4]
+}
+
+impl std::fmt::Display for Mat4 {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Mat4(")?;
+        for (i, row) in self.m.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "[{:.2}, {:.2}, {:.2}, {:.2}]", row[0], row[1], row[2], row[3])?;
+        }
+        write!(f, ")")
+    }
+}
+
+impl PartialEq for Mat4 {
+    fn eq(&self, other: &Mat4) -> bool {
+        self.m == other.m
+    }
+}
+
+impl Mat4 {
+    pub fn new(m: [[f64; 4]; 4]) -> Mat4 {
+        Mat4 { m }
+    }
+
+    pub fn identity() -> Mat4 {
+        Mat4::from_diagonal(Vec4::new(1.0, 1.0, 1.0, 1.0))
+    }
+
+    pub fn from_diagonal(v: Vec4) -> Mat4 {
+        Mat4::new([
+            [v.x, 0.0, 0.0, 0.0],
+            [0.0, v.y, 0.0, 0.0],
+            [0.0, 0.0, v.z, 0.0],
+            [0.0, 0.0, 0.0, v.w]
+        ])
+    }
+
+    pub fn diagonal(&self) -> Vec4 {
+        Vec4::new(self.m[0][0], self.m[1][1], self.m[2][2], self.m[3][3])
+    }
+
+    pub fn from_translation(t: Vec3) -> Mat4 {
+        Mat4::new([
+            [1.0, 0.0, 0.0, t.x],
+            [0.0, 1.0, 0.0, t.y],
+            [0.0, 0.0, 1.0, t.z],
+            [0.0, 0.0, 0.0, 1.0]
+        ])
+    }
+
+    pub fn from_scale(s: Vec3) -> Mat4 {
+        Mat4::from_diagonal(Vec4::new(s.x, s.y, s.z, 1.0))
+    }
+
+    pub fn from_rotation_x(angle: f64) -> Mat4 {
+        let (s, c) = angle.sin_cos();
+        Mat4::new([
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, c, -s, 0.0],
+            [0.0, s, c, 0.0],
+            [0.0, 0.0, 0.0, 1.0]
+        ])
+    }
+
+    pub fn from_rotation_y(angle: f64) -> Mat4 {
+        let (s, c) = angle.sin_cos();
+        Mat4::new([
+            [c, 0.0, s, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [-s, 0.0, c, 0.0],
+            [0.0, 0.0, 0.0, 1.0]
+        ])
+    }
+
+    pub fn from_rotation_z(angle: f64) -> Mat4 {
+        let (s, c) = angle.sin_cos();
+        Mat4::new([
+            [c, -s, 0.0, 0.0],
+            [s, c, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0]
+        ])
+    }
+
+    // Same convention as Quaternion::from_euler: Ry(yaw) * Rx(pitch) * Rz(roll)
+    pub fn from_euler(roll: f64, pitch: f64, yaw: f64) -> Mat4 {
+        Mat4::from_rotation_y(yaw) * Mat4::from_rotation_x(pitch) * Mat4::from_rotation_z(roll)
+    }
+
+    // Rotation matrix of a unit quaternion
+    pub fn from_quaternion(q: Quaternion) -> Mat4 {
+        Mat4::from(Mat3::from_quaternion(q))
+    }
+
+    // Rotation part as a unit quaternion, the upper-left 3x3 must be orthonormal
+    pub fn to_quaternion(&self) -> Quaternion {
+        self.to_mat3().to_quaternion()
+    }
+
+    // Upper-left 3x3 (rotation and scale), translation and projection are dropped
+    pub fn to_mat3(&self) -> Mat3 {
+        let mut r = Mat3::new([[0.0; 3]; 3]);
+        for i in 0..3 {
+            r.m[i].copy_from_slice(&self.m[i][0..3]);
+        }
+        r
+    }
+
+    // Scale, then rotate, then translate: T * R * S
+    pub fn from_trs(translation: Vec3, rotation: Quaternion, scale: Vec3) -> Mat4 {
+        Mat4::from_translation(translation)
+            * Mat4::from_quaternion(rotation)
+            * Mat4::from_scale(scale)
+    }
+
+    // Right-handed view matrix: the camera sits at eye and looks down its -Z axis
+    pub fn look_at(eye: Vec3, target: Vec3, up: Vec3) -> Mat4 {
+        let f = (target - eye).get_normalize();
+        let s = f.cross(&up).get_normalize();
+        let u = s.cross(&f);
+        Mat4::new([
+            [s.x, s.y, s.z, -s.dot(&eye)],
+            [u.x, u.y, u.z, -u.dot(&eye)],
+            [-f.x, -f.y, -f.z, f.dot(&eye)],
+            [0.0, 0.0, 0.0, 1.0]
+        ])
+    }
+
+    // OpenGL style projection, fovy in radians, depth mapped to [-1, 1]
+    pub fn perspective(fovy: f64, aspect: f64, near: f64, far: f64) -> Mat4 {
+        let f = 1.0 / (fovy * 0.5).tan();
+        Mat4::new([
+            [f / aspect, 0.0, 0.0, 0.0],
+            [0.0, f, 0.0, 0.0],
+            [0.0, 0.0, (far + near) / (near - far), 2.0 * far * near / (near - far)],
+            [0.0, 0.0, -1.0, 0.0]
+        ])
+    }
+
+    // perspective * look_at
+    pub fn view_projection(eye: Vec3, target: Vec3, up: Vec3,
+        fovy: f64, aspect: f64, near: f64, far: f64) -> Mat4 {
+        Mat4::perspective(fovy, aspect, near, far) * Mat4::look_at(eye, target, up)
+    }
+
+    // Point with w = 1: translation applies, followed by the perspective divide
+    pub fn transform_point(&self, p: &Vec3) -> Vec3 {
+        (*self * Vec4::new(p.x, p.y, p.z, 1.0)).to_vec3()
+    }
+
+    // Direction with w = 0: translation is ignored
+    pub fn transform_vector(&self, v: &Vec3) -> Vec3 {
+        (*self * Vec4::new(v.x, v.y, v.z, 0.0)).to_pure_vec3()
+    }
+
+    pub fn transpose(&self) -> Mat4 {
+        let mut r = *self;
+        for i in 0..4 {
+            for j in 0..4 {
+                r.m[i][j] = self.m[j][i];
+            }
+        }
+        r
+    }
+
+    // Cofactor expansion along the first row
+    pub fn determinant(&self) -> f64 {
+        (0..4).map(|j| self.m[0][j] * self.cofactor(0, j)).sum()
+    }
+
+    // Adjugate divided by the determinant
+    // Returns None if the matrix is singular
+    pub fn inverse(&self) -> Option<Mat4> {
+        let det = self.determinant();
+        if self.is_singular(det) {
+            return None;
+        }
+        let mut r = Mat4::new([[0.0; 4]; 4]);
+        for i in 0..4 {
+            for j in 0..4 {
+                r.m[i][j] = self.cofactor(j, i) / det;
+            }
+        }
+        Some(r)
+    }
+
+    // Inverse-transpose of the upper-left 3x3, the matrix normals must be
+    // transformed by when the model matrix has a non-uniform scale
+    // A singular matrix keeps the unscaled cofactors, whose rows are cross
+    // products of the rows (normals need renormalizing anyway)
+    pub fn normal_matrix(&self) -> Mat3 {
+        let upper = self.to_mat3();
+        if let Some(inv) = upper.inverse() {
+            return inv.transpose();
+        }
+        let row = |i: usize| Vec3::new(upper.m[i][0], upper.m[i][1], upper.m[i][2]);
+        let (r0, r1, r2) = (row(0), row(1), row(2));
+        let mut r = Mat3::new([[0.0; 3]; 3]);
+        for (i, v) in [r1.cross(&r2), r2.cross(&r0), r0.cross(&r1)].iter().enumerate() {
+            r.m[i] = [v.x, v.y, v.z];
+        }
+        r
+    }
+
+    // The determinant scales with the fourth power of the entries,
+    // so the cutoff is relative to the largest one
+    fn is_singular(&self, det: f64) -> bool {
+        let max = self.m.iter().flatten().fold(0.0f64, |a, b| a.max(b.abs()));
+        det.abs() <= f64::EPSILON * max.powi(4)
+    }
+
+    fn cofactor(&self, row: usize, col: usize) -> f64 {
+        let mut minor = Mat3::new([[0.0; 3]; 3]);
+        let others = |skip: usize| (0..4).filter(move |&k| k != skip);
+        for (i, r) in others(row).enumerate() {
+            for (j, c) in others(col).enumerate() {
+                minor.m[i][j] = self.m[r][c];
+            }
+        }
+        let sign = [1.0, -1.0][(row + col) % 2];
+        sign * minor.determinant()
+    }
+
+    // r[row][col], same layout as the internal storage
+    pub fn to_row_major(&self) -> [[f64; 4]; 4] {
+        self.m
+    }
+
+    // r[col][row], the layout expected by OpenGL like APIs
+    pub fn to_column_major(&self) -> [[f64; 4]; 4] {
+        self.transpose().m
+    }
+
+    pub fn to_row_major_flat(&self) -> [f64; 16] {
+        flatten(&self.to_row_major())
+    }
+
+    pub fn to_column_major_flat(&self) -> [f64; 16] {
+        flatten(&self.to_column_major())
+    }
+}
+
+// World point to window coordinates (gluProject convention)
+// viewport is (x, y, width, height), the depth is mapped to [0, 1]
+// None if the point is on the camera plane (w = 0)
+pub fn project(world: Vec3, view: &Mat4, proj: &Mat4, viewport: (f64, f64, f64, f64)) -> Option<Vec3> {
+    let ndc = (*proj * *view * Vec4::new(world.x, world.y, world.z, 1.0)).to_vec3_checked()?;
+    let (x, y, width, height) = viewport;
+    Some(Vec3::new(
+        x + (ndc.x + 1.0) * 0.5 * width,
+        y + (ndc.y + 1.0) * 0.5 * height,
+        (ndc.z + 1.0) * 0.5
+    ))
+}
+
+// Inverse of project: window coordinates and depth back to world space
+// None if proj * view is singular or the result has w = 0
+pub fn unproject(screen: Vec3, view: &Mat4, proj: &Mat4, viewport: (f64, f64, f64, f64)) -> Option<Vec3> {
+    let inverse = (*proj * *view).inverse()?;
+    let (x, y, width, height) = viewport;
+    let ndc = Vec4::new(
+        (screen.x - x) / width * 2.0 - 1.0,
+        (screen.y - y) / height * 2.0 - 1.0,
+        screen.z * 2.0 - 1.0,
+        1.0
+    );
+    (inverse * ndc).to_vec3_checked()
+}
+
+fn flatten(m: &[[f64; 4]; 4]) -> [f64; 16] {
+    let mut r = [0.0; 16];
+    for (i, row) in m.iter().enumerate() {
+        r[i * 4..i * 4 + 4].copy_from_slice(row);
+    }
+    r
+}
+
+// Embeds a 3x3 linear map: no translation and w row (0, 0, 0, 1)
+impl From<Mat3> for Mat4 {
+    fn from(r: Mat3) -> Mat4 {
+        let r = r.m;
+        Mat4::new([
+            [r[0][0], r[0][1], r[0][2], 0.0],
+            [r[1][0], r[1][1], r[1][2], 0.0],
+            [r[2][0], r[2][1], r[2][2], 0.0],
+            [0.0, 0.0, 0.0, 1.0]
+        ])
+    }
+}
+
+impl From<Quaternion> for Mat4 {
+    fn from(q: Quaternion) -> Mat4 {
+        Mat4::from_quaternion(q)
+    }
+}
+
+impl Mul<Mat4> for Mat4 {
+    type Output = Mat4;
+
+    fn mul(self, other: Mat4) -> Mat4 {
+        let mut r = Mat4::new([[0.0; 4]; 4]);
+        for i in 0..4 {
+            for j in 0..4 {
+                r.m[i][j] = (0..4).map(|k| self.m[i][k] * other.m[k][j]).sum();
+            }
+        }
+        r
+    }
+}
+
+impl Mul<Vec4> for Mat4 {
+    type Output = Vec4;
+
+    fn mul(self, v: Vec4) -> Vec4 {
+        let m = &self.m;
+        Vec4::new(
+            m[0][0] * v.x + m[0][1] * v.y + m[0][2] * v.z + m[0][3] * v.w,
+            m[1][0] * v.x + m[1][1] * v.y + m[1][2] * v.z + m[1][3] * v.w,
+            m[2][0] * v.x + m[2][1] * v.y + m[2][2] * v.z + m[2][3] * v.w,
+            m[3][0] * v.x + m[3][1] * v.y + m[3][2] * v.z + m[3][3] * v.w
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Mat4, project, unproject};
+    use crate::matrix::matrix3::Mat3;
+    use crate::vector::vector3::Vec3;
+    use crate::vector::vector4::Vec4;
+    use crate::vector::quaternions::Quaternion;
+    use std::f64::consts::PI;
+
+    fn assert_vec4_eq(a: Vec4, b: Vec4) {
+        assert_approx_eq::assert_approx_eq!(a.x, b.x, 0.000001);
+        assert_approx_eq::assert_approx_eq!(a.y, b.y, 0.000001);
+        assert_approx_eq::assert_approx_eq!(a.z, b.z, 0.000001);
+        assert_approx_eq::assert_approx_eq!(a.w, b.w, 0.000001);
+    }
+
+    fn assert_mat4_eq(a: Mat4, b: Mat4) {
+        for i in 0..4 {
+            for j in 0..4 {
+                assert_approx_eq::assert_approx_eq!(a.m[i][j], b.m[i][j], 0.000001);
+            }
+        }
+    }
+
+    fn translate_rotate_z() -> Mat4 {
+        Mat4::from_translation(Vec3::new(3.0, -2.0, 5.0)) * Mat4::from_rotation_z(0.6)
+    }
+
+    #[test]
+    fn test_identity() {
+        let v = Vec4::new(1.0, 2.0, 3.0, 1.0);
+        assert_eq!(Mat4::identity() * v, v);
+    }
+
+    #[test]
+    fn test_mul_vec4() {
+        let m = Mat4::new([
+            [1.0, 0.0, 0.0, 5.0],
+            [0.0, 1.0, 0.0, 6.0],
+            [0.0, 0.0, 1.0, 7.0],
+            [0.0, 0.0, 0.0, 1.0]
+        ]);
+        assert_eq!(m * Vec4::new(1.0, 2.0, 3.0, 1.0), Vec4::new(6.0, 8.0, 10.0, 1.0));
+    }
+
+    #[test]
+    fn test_from_diagonal() {
+        let m = Mat4::from_diagonal(Vec4::new(2.0, 3.0, 4.0, 1.0));
+        assert_eq!(m * Vec4::new(1.0, 0.0, 0.0, 0.0), Vec4::new(2.0, 0.0, 0.0, 0.0));
+        assert_eq!(m * Vec4::new(0.0, 1.0, 0.0, 0.0), Vec4::new(0.0, 3.0, 0.0, 0.0));
+        assert_eq!(m * Vec4::new(0.0, 0.0, 1.0, 0.0), Vec4::new(0.0, 0.0, 4.0, 0.0));
+        assert_eq!(m.diagonal(), Vec4::new(2.0, 3.0, 4.0, 1.0));
+    }
+
+    #[test]
+    fn test_row_column_major() {
+        let m = Mat4::new([
+            [1.0, 2.0, 3.0, 4.0],
+            [5.0, 6.0, 7.0, 8.0],
+            [9.0, 10.0, 11.0, 12.0],
+            [13.0, 14.0, 15.0, 16.0]
+        ]);
+        let rows = m.to_row_major();
+        let cols = m.to_column_major();
+        for i in 0..4 {
+            for j in 0..4 {
+                assert_eq!(rows[i][j], cols[j][i]);
+            }
+        }
+        assert_eq!(rows[0][3], 4.0);
+        assert_eq!(cols[3][0], 4.0);
+        assert_eq!(m.transpose().to_row_major(), cols);
+    }
+
+    #[test]
+    fn test_row_column_major_flat() {
+        let m = Mat4::new([
+            [1.0, 2.0, 3.0, 4.0],
+            [5.0, 6.0, 7.0, 8.0],
+            [9.0, 10.0, 11.0, 12.0],
+            [13.0, 14.0, 15.0, 16.0]
+        ]);
+        let rows = m.to_row_major_flat();
+        let cols = m.to_column_major_flat();
+        assert_eq!(rows[..4], [1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(cols[..4], [1.0, 5.0, 9.0, 13.0]);
+        for i in 0..4 {
+            for j in 0..4 {
+                assert_eq!(rows[i * 4 + j], cols[j * 4 + i]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_mul_mat4() {
+        let m = translate_rotate_z();
+        assert_eq!(m * Mat4::identity(), m);
+        assert_eq!(Mat4::identity() * m, m);
+        let v = Vec4::new(1.0, 2.0, 3.0, 1.0);
+        let d = Mat4::from_diagonal(Vec4::new(2.0, 3.0, 4.0, 1.0));
+        assert_eq!((d * d) * v, d * (d * v));
+    }
+
+    #[test]
+    fn test_determinant() {
+        assert_eq!(Mat4::from_diagonal(Vec4::new(2.0, 3.0, 4.0, 5.0)).determinant(), 120.0);
+        assert_approx_eq::assert_approx_eq!(translate_rotate_z().determinant(), 1.0, 0.000001);
+        let m = Mat4::new([
+            [1.0, 2.0, 3.0, 4.0],
+            [5.0, 6.0, 7.0, 8.0],
+            [2.0, 0.0, 1.0, 3.0],
+            [1.0, 1.0, 0.0, 2.0]
+        ]);
+        assert_approx_eq::assert_approx_eq!(m.determinant(), -40.0, 0.000001);
+    }
+
+    #[test]
+    fn test_inverse() {
+        let m = translate_rotate_z();
+        let inv = m.inverse().unwrap();
+        assert_mat4_eq(m * inv, Mat4::identity());
+        assert_mat4_eq(inv * m, Mat4::identity());
+
+        let p = Vec4::new(1.0, 2.0, 3.0, 1.0);
+        let back = inv * (m * p);
+        assert_approx_eq::assert_approx_eq!(back.x, p.x, 0.000001);
+        assert_approx_eq::assert_approx_eq!(back.y, p.y, 0.000001);
+        assert_approx_eq::assert_approx_eq!(back.z, p.z, 0.000001);
+    }
+
+    #[test]
+    fn test_inverse_singular() {
+        let m = Mat4::new([
+            [1.0, 2.0, 3.0, 4.0],
+            [2.0, 4.0, 6.0, 8.0],
+            [0.0, 1.0, 0.0, 1.0],
+            [1.0, 0.0, 1.0, 0.0]
+        ]);
+        assert!(m.inverse().is_none());
+    }
+
+    #[test]
+    fn test_inverse_small_scale() {
+        let m = Mat4::new([
+            [1e-4, 0.0, 0.0, 0.0],
+            [0.0, 1e-4, 0.0, 0.0],
+            [0.0, 0.0, 1e-4, 0.0],
+            [0.0, 0.0, 0.0, 1e-4]
+        ]);
+        let inv = m.inverse().unwrap();
+        assert_mat4_eq(m * inv, Mat4::identity());
+    }
+
+    #[test]
+    fn test_from_translation() {
+        let m = Mat4::from_translation(Vec3::new(1.0, 2.0, 3.0));
+        assert_eq!(m * Vec4::new(1.0, 1.0, 1.0, 1.0), Vec4::new(2.0, 3.0, 4.0, 1.0));
+        assert_eq!(m * Vec4::new(1.0, 1.0, 1.0, 0.0), Vec4::new(1.0, 1.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn test_from_scale() {
+        let m = Mat4::from_scale(Vec3::new(2.0, 3.0, 4.0));
+        assert_eq!(m * Vec4::new(1.0, 1.0, 1.0, 1.0), Vec4::new(2.0, 3.0, 4.0, 1.0));
+    }
+
+    #[test]
+    fn test_from_rotation() {
+        let x = Vec4::new(1.0, 0.0, 0.0, 1.0);
+        let y = Vec4::new(0.0, 1.0, 0.0, 1.0);
+        let z = Vec4::new(0.0, 0.0, 1.0, 1.0);
+        assert_vec4_eq(Mat4::from_rotation_x(PI / 2.0) * y, z);
+        assert_vec4_eq(Mat4::from_rotation_y(PI / 2.0) * z, x);
+        assert_vec4_eq(Mat4::from_rotation_z(PI / 2.0) * x, y);
+    }
+
+    #[test]
+    fn test_translate_then_rotate() {
+        let m = Mat4::from_rotation_z(PI / 2.0) * Mat4::from_translation(Vec3::new(1.0, 0.0, 0.0));
+        assert_vec4_eq(m * Vec4::new(1.0, 0.0, 0.0, 1.0), Vec4::new(0.0, 2.0, 0.0, 1.0));
+    }
+
+    #[test]
+    fn test_from_quaternion() {
+        let q = Quaternion::from_axis_angle(Vec3::new(0.0, 1.0, 0.0), 0.8);
+        let m = Mat4::from_quaternion(q);
+        let r = Mat4::from_rotation_y(0.8);
+        for i in 0..4 {
+            for j in 0..4 {
+                assert_approx_eq::assert_approx_eq!(m.m[i][j], r.m[i][j], 0.000001);
+            }
+        }
+    }
+
+    #[test]
+    fn test_from_trs() {
+        let q = Quaternion::from_axis_angle(Vec3::new(0.0, 0.0, 1.0), PI / 2.0);
+        let m = Mat4::from_trs(Vec3::new(10.0, 0.0, 0.0), q, Vec3::new(2.0, 2.0, 2.0));
+        assert_vec4_eq(m * Vec4::new(1.0, 0.0, 0.0, 1.0), Vec4::new(10.0, 2.0, 0.0, 1.0));
+    }
+
+    #[test]
+    fn test_normal_matrix() {
+        let rotation = Quaternion::from_axis_angle(Vec3::new(0.0, 1.0, 0.0), 0.4);
+        let model = Mat4::from_trs(Vec3::new(1.0, 2.0, 3.0), rotation, Vec3::new(2.0, 1.0, 0.5));
+
+        // Surface spanned by two tangents with normal (1, 1, 0)
+        let t1 = Vec4::new(1.0, -1.0, 0.0, 0.0);
+        let t2 = Vec4::new(0.0, 0.0, 1.0, 0.0);
+        let n = Vec3::new(1.0, 1.0, 0.0);
+
+        let mt1 = (model * t1).to_pure_vec3();
+        let mt2 = (model * t2).to_pure_vec3();
+        let mn = model.normal_matrix() * n;
+        assert_approx_eq::assert_approx_eq!(mn.dot(&mt1), 0.0, 0.000001);
+        assert_approx_eq::assert_approx_eq!(mn.dot(&mt2), 0.0, 0.000001);
+
+        // Using the model matrix directly breaks perpendicularity
+        let wrong = (model * Vec4::new(n.x, n.y, n.z, 0.0)).to_pure_vec3();
+        assert!(wrong.dot(&mt1).abs() > 0.1);
+
+        let expected = {
+            let mut upper = Mat3::new([[0.0; 3]; 3]);
+            for i in 0..3 {
+                for j in 0..3 {
+                    upper.m[i][j] = model.m[i][j];
+                }
+            }
+            upper.inverse().unwrap().transpose()
+        };
+        let r = model.normal_matrix();
+        for i in 0..3 {
+            for j in 0..3 {
+                assert_approx_eq::assert_approx_eq!(r.m[i][j], expected.m[i][j], 0.000001);
+            }
+        }
+    }
+
+    #[test]
+    fn test_normal_matrix_small_scale() {
+        let r = Mat4::from_scale(Vec3::new(1e-6, 1e-6, 1e-6)).normal_matrix();
+        for i in 0..3 {
+            for j in 0..3 {
+                let expected = if i == j { 1.0 } else { 0.0 };
+                assert_approx_eq::assert_approx_eq!(r.m[i][j] * 1e-6, expected, 0.000001);
+            }
+        }
+    }
+
+    #[test]
+    fn test_quaternion_round_trip() {
+        let axes = [
+            Vec3::new(1.0, 0.0, 0.0),
+            Vec3::new(0.0, -1.0, 0.0),
+            Vec3::new(2.0, 1.0, -3.0)
+        ];
+        for axis in axes.iter() {
+            for angle in [0.0, 0.3, 1.7, PI].iter() {
+                let q = Quaternion::from_axis_angle(*axis, *angle);
+                let m: Mat4 = q.into();
+                assert_eq!(m, Mat4::from_quaternion(q));
+                let r = m.to_quaternion();
+                let sign = if r.dot(&q) < 0.0 { -1.0 } else { 1.0 };
+                assert_approx_eq::assert_approx_eq!(r.v.x * sign, q.v.x, 0.000001);
+                assert_approx_eq::assert_approx_eq!(r.v.y * sign, q.v.y, 0.000001);
+                assert_approx_eq::assert_approx_eq!(r.v.z * sign, q.v.z, 0.000001);
+                assert_approx_eq::assert_approx_eq!(r.s * sign, q.s, 0.000001);
+            }
+        }
+
+        // The translation does not affect the rotation
+        let q = Quaternion::from_axis_angle(Vec3::new(0.0, 0.0, 1.0), 0.6);
+        let r = Mat4::from_trs(Vec3::new(4.0, 5.0, 6.0), q, Vec3::new(1.0, 1.0, 1.0)).to_quaternion();
+        assert_approx_eq::assert_approx_eq!(r.v.z, q.v.z, 0.000001);
+        assert_approx_eq::assert_approx_eq!(r.s, q.s, 0.000001);
+    }
+
+    #[test]
+    fn test_look_at() {
+        let eye = Vec3::new(1.0, 2.0, 3.0);
+        let view = Mat4::look_at(eye, Vec3::new(1.0, 2.0, -7.0), Vec3::new(0.0, 1.0, 0.0));
+        assert_vec4_eq(view * Vec4::new(1.0, 2.0, 3.0, 1.0), Vec4::new(0.0, 0.0, 0.0, 1.0));
+        assert_vec4_eq(view * Vec4::new(1.0, 2.0, -7.0, 1.0), Vec4::new(0.0, 0.0, -10.0, 1.0));
+
+        let view = Mat4::look_at(Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0));
+        assert_vec4_eq(view * Vec4::new(2.0, 0.0, 0.0, 1.0), Vec4::new(0.0, 0.0, -2.0, 1.0));
+        assert_vec4_eq(view * Vec4::new(0.0, 0.0, 1.0, 1.0), Vec4::new(1.0, 0.0, 0.0, 1.0));
+    }
+
+    #[test]
+    fn test_perspective() {
+        let proj = Mat4::perspective(PI / 2.0, 2.0, 1.0, 10.0);
+        let ndc = |p: Vec4| {
+            let c = proj * p;
+            Vec3::new(c.x / c.w, c.y / c.w, c.z / c.w)
+        };
+        assert_approx_eq::assert_approx_eq!(ndc(Vec4::new(0.0, 0.0, -1.0, 1.0)).z, -1.0, 0.000001);
+        assert_approx_eq::assert_approx_eq!(ndc(Vec4::new(0.0, 0.0, -10.0, 1.0)).z, 1.0, 0.000001);
+        // 90 degrees vertical field: the top of the frustum is at y = -z
+        assert_approx_eq::assert_approx_eq!(ndc(Vec4::new(0.0, 5.0, -5.0, 1.0)).y, 1.0, 0.000001);
+        assert_approx_eq::assert_approx_eq!(ndc(Vec4::new(10.0, 0.0, -5.0, 1.0)).x, 1.0, 0.000001);
+    }
+
+    #[test]
+    fn test_view_projection() {
+        let eye = Vec3::new(3.0, 4.0, 5.0);
+        let target = Vec3::new(0.0, 1.0, 0.0);
+        let up = Vec3::new(0.0, 1.0, 0.0);
+        let vp = Mat4::view_projection(eye, target, up, 1.0, 16.0 / 9.0, 0.1, 100.0);
+        let expected = Mat4::perspective(1.0, 16.0 / 9.0, 0.1, 100.0) * Mat4::look_at(eye, target, up);
+        assert_mat4_eq(vp, expected);
+    }
+
+    #[test]
+    fn test_from_euler() {
+        let angles = [(0.0, 0.0, 0.0), (0.3, -0.7, 1.2), (PI / 2.0, 0.1, -2.5), (-1.0, PI / 2.0, 0.4)];
+        for &(roll, pitch, yaw) in angles.iter() {
+            let m = Mat4::from_euler(roll, pitch, yaw);
+            assert_mat4_eq(m, Mat4::from_quaternion(Quaternion::from_euler(roll, pitch, yaw)));
+        }
+
+        // Yaw only turns -Z (forward) toward -X
+        let m = Mat4::from_euler(0.0, 0.0, PI / 2.0);
+        assert_vec4_eq(m * Vec4::new(0.0, 0.0, -1.0, 0.0), Vec4::new(-1.0, 0.0, 0.0, 0.0));
+        // Roll is applied before pitch
+        let m = Mat4::from_euler(PI / 2.0, PI / 2.0, 0.0);
+        assert_vec4_eq(m * Vec4::new(1.0, 0.0, 0.0, 0.0), Vec4::new(0.0, 0.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn test_mat3_round_trip() {
+        let r = Mat3::from_quaternion(Quaternion::from_axis_angle(Vec3::new(1.0, 2.0, 3.0), 0.8));
+        let m = Mat4::from(r);
+        assert_eq!(m.m[3], [0.0, 0.0, 0.0, 1.0]);
+        assert_eq!([m.m[0][3], m.m[1][3], m.m[2][3]], [0.0, 0.0, 0.0]);
+        assert_eq!(m.to_mat3(), r);
+
+        let v = Vec3::new(-1.0, 0.5, 2.0);
+        let rv = r * v;
+        assert_vec4_eq(m * Vec4::new(v.x, v.y, v.z, 1.0), Vec4::new(rv.x, rv.y, rv.z, 1.0));
+
+        // Translation is dropped
+        let t = Mat4::from_translation(Vec3::new(1.0, 2.0, 3.0)) * m;
+        assert_eq!(t.to_mat3(), r);
+    }
+
+    #[test]
+    fn test_transform_point_vector() {
+        let t = Mat4::from_translation(Vec3::new(1.0, 2.0, 3.0));
+        let v = Vec3::new(4.0, -1.0, 0.5);
+        assert_eq!(t.transform_point(&v), Vec3::new(5.0, 1.0, 3.5));
+        assert_eq!(t.transform_vector(&v), v);
+
+        let m = translate_rotate_z();
+        let p = m.transform_point(&Vec3::new(1.0, 0.0, 0.0));
+        let d = m.transform_vector(&Vec3::new(1.0, 0.0, 0.0));
+        assert_approx_eq::assert_approx_eq!(p.x - d.x, 3.0, 0.000001);
+        assert_approx_eq::assert_approx_eq!(p.y - d.y, -2.0, 0.000001);
+
+        // Projective matrices divide by w
+        let proj = Mat4::perspective(PI / 2.0, 1.0, 1.0, 10.0);
+        let ndc = proj.transform_point(&Vec3::new(0.0, 2.0, -2.0));
+        assert_approx_eq::assert_approx_eq!(ndc.y, 1.0, 0.000001);
+    }
+
+    #[test]
+    fn test_project_unproject() {
+        let view = Mat4::look_at(Vec3::new(2.0, 3.0, 6.0), Vec3::new(0.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0));
+        let proj = Mat4::perspective(PI / 3.0, 16.0 / 9.0, 0.1, 100.0);
+        let viewport = (0.0, 0.0, 1920.0, 1080.0);
+
+        let world = Vec3::new(0.5, -0.25, 1.0);
+        let screen = project(world, &view, &proj, viewport).unwrap();
+        assert!(screen.z > 0.0 && screen.z < 1.0);
+        let back = unproject(screen, &view, &proj, viewport).unwrap();
+        assert_approx_eq::assert_approx_eq!(back.x, world.x, 0.00001);
+        assert_approx_eq::assert_approx_eq!(back.y, world.y, 0.00001);
+        assert_approx_eq::assert_approx_eq!(back.z, world.z, 0.00001);
+
+        // The look-at target lands in the middle of the viewport
+        let center = project(Vec3::new(0.0, 0.0, 0.0), &view, &proj, (10.0, 20.0, 200.0, 100.0)).unwrap();
+        assert_approx_eq::assert_approx_eq!(center.x, 110.0, 0.000001);
+        assert_approx_eq::assert_approx_eq!(center.y, 70.0, 0.000001);
+
+        let singular = Mat4::new([[0.0; 4]; 4]);
+        assert_eq!(unproject(screen, &view, &singular, viewport), None);
+    }
+}
@@ -0,0 +1 @@
+pub mod rigid2d;
@@ -0,0 +1,143 @@
+// Helpers for 2D rigid bodies
+// Angular quantities are scalars (rotation around the z axis)
+use crate::vector::vector2::{Vec2, cross_scalar};
+
+// Velocity of a point at offset `r` from the center of mass: v + omega x r
+pub fn point_velocity_2d(linear_vel: Vec2, angular_vel: f64, r: Vec2) -> Vec2 {
+    linear_vel + cross_scalar(angular_vel, r)
+}
+
+// State of a body needed to solve a contact
+// Inverse mass and inertia are used so static bodies can use 0
+#[derive(Clone, Copy, Debug)]
+pub struct Body2d {
+    pub velocity: Vec2,
+    pub angular_velocity: f64,
+    pub inv_mass: f64,
+    pub inv_inertia: f64
+}
+
+impl Body2d {
+    pub fn new(velocity: Vec2, angular_velocity: f64, inv_mass: f64, inv_inertia: f64) -> Body2d {
+        Body2d { velocity, angular_velocity, inv_mass, inv_inertia }
+    }
+
+    pub fn point_velocity(&self, r: Vec2) -> Vec2 {
+        point_velocity_2d(self.velocity, self.angular_velocity, r)
+    }
+
+    // Applies an impulse at offset `r` from the center of mass
+    pub fn apply_impulse(&mut self, impulse: Vec2, r: Vec2) {
+        self.velocity += impulse * self.inv_mass;
+        self.angular_velocity += r.cross(&impulse) * self.inv_inertia;
+    }
+}
+
+// Sequential impulse resolution of a single contact between a and b
+// r_a / r_b: contact point offsets from each center of mass
+// normal: unit contact normal pointing from a to b
+// Returns the impulse to apply to b, a receives the opposite impulse
+// Bodies already separating along the normal get no impulse
+pub fn resolve_contact_2d(
+    a: &Body2d,
+    b: &Body2d,
+    r_a: Vec2,
+    r_b: Vec2,
+    normal: Vec2,
+    restitution: f64
+) -> Vec2 {
+    let relative = b.point_velocity(r_b) - a.point_velocity(r_a);
+    let normal_vel = relative.scalar(&normal);
+    if normal_vel > 0.0 {
+        return Vec2::new(0.0, 0.0);
+    }
+
+    let ra_n = r_a.cross(&normal);
+    let rb_n = r_b.cross(&normal);
+    let inv_mass_sum = a.inv_mass + b.inv_mass
+        + ra_n * ra_n * a.inv_inertia
+        + rb_n * rb_n * b.inv_inertia;
+    if inv_mass_sum == 0.0 {
+        return Vec2::new(0.0, 0.0);
+    }
+    normal * (-(1.0 + restitution) * normal_vel / inv_mass_sum)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Body2d, point_velocity_2d, resolve_contact_2d};
+    use crate::vector::vector2::Vec2;
+
+    #[test]
+    fn test_point_velocity_translation() {
+        let v = Vec2::new(1.0, -2.0);
+        assert_eq!(point_velocity_2d(v, 0.0, Vec2::new(3.0, 4.0)), v);
+    }
+
+    #[test]
+    fn test_point_velocity_spin() {
+        let r = Vec2::new(3.0, 4.0);
+        let v = point_velocity_2d(Vec2::new(0.0, 0.0), -2.0, r);
+        assert_eq!(v.scalar(&r), 0.0);
+        assert_approx_eq::assert_approx_eq!(v.norm(), 2.0 * 5.0, 0.000001);
+        // Counterclockwise spin moves +x points toward +y
+        assert_eq!(point_velocity_2d(Vec2::new(0.0, 0.0), 1.0, Vec2::UNIT_X), Vec2::UNIT_Y);
+    }
+
+    #[test]
+    fn test_resolve_contact_elastic_swap() {
+        let zero = Vec2::new(0.0, 0.0);
+        let normal = Vec2::new(1.0, 0.0);
+        let mut a = Body2d::new(Vec2::new(1.0, 0.0), 0.0, 1.0, 1.0);
+        let mut b = Body2d::new(Vec2::new(-1.0, 0.0), 0.0, 1.0, 1.0);
+
+        let impulse = resolve_contact_2d(&a, &b, zero, zero, normal, 1.0);
+        a.apply_impulse(-impulse, zero);
+        b.apply_impulse(impulse, zero);
+        assert_eq!(a.velocity, Vec2::new(-1.0, 0.0));
+        assert_eq!(b.velocity, Vec2::new(1.0, 0.0));
+    }
+
+    #[test]
+    fn test_resolve_contact_inelastic() {
+        let zero = Vec2::new(0.0, 0.0);
+        let normal = Vec2::new(0.0, 1.0);
+        let mut a = Body2d::new(Vec2::new(0.0, 2.0), 0.0, 1.0, 0.0);
+        let mut b = Body2d::new(Vec2::new(0.0, 0.0), 0.0, 1.0, 0.0);
+
+        let impulse = resolve_contact_2d(&a, &b, zero, zero, normal, 0.0);
+        a.apply_impulse(-impulse, zero);
+        b.apply_impulse(impulse, zero);
+        assert_eq!(a.velocity, b.velocity);
+        assert_eq!(a.velocity, Vec2::new(0.0, 1.0));
+    }
+
+    #[test]
+    fn test_resolve_contact_static_and_separating() {
+        let normal = Vec2::new(0.0, -1.0);
+        let ground = Body2d::new(Vec2::new(0.0, 0.0), 0.0, 0.0, 0.0);
+        let mut ball = Body2d::new(Vec2::new(0.5, -3.0), 0.0, 1.0, 0.0);
+        let zero = Vec2::new(0.0, 0.0);
+
+        // Normal goes from the ball (a) to the ground (b)
+        let impulse = resolve_contact_2d(&ball, &ground, zero, zero, normal, 1.0);
+        ball.apply_impulse(-impulse, zero);
+        assert_eq!(ball.velocity, Vec2::new(0.5, 3.0));
+
+        let impulse = resolve_contact_2d(&ball, &ground, zero, zero, normal, 1.0);
+        assert_eq!(impulse, zero);
+    }
+
+    #[test]
+    fn test_resolve_contact_with_offset_spins() {
+        let normal = Vec2::new(1.0, 0.0);
+        let mut a = Body2d::new(Vec2::new(1.0, 0.0), 0.0, 1.0, 1.0);
+        let b = Body2d::new(Vec2::new(0.0, 0.0), 0.0, 0.0, 0.0);
+        let r_a = Vec2::new(0.0, 1.0);
+
+        let impulse = resolve_contact_2d(&a, &b, r_a, Vec2::new(0.0, 0.0), normal, 0.0);
+        a.apply_impulse(-impulse, r_a);
+        assert_approx_eq::assert_approx_eq!(a.point_velocity(r_a).scalar(&normal), 0.0, 0.000001);
+        assert!(a.angular_velocity != 0.0);
+    }
+}
@@ -0,0 +1,38 @@
+// Re-exports of the most used types
+// use salg::prelude::*;
+pub use crate::vector::vector2::Vec2;
+pub use crate::vector::vector3::Vec3;
+pub use crate::vector::vector4::Vec4;
+pub use crate::vector::quaternions::Quaternion;
+pub use crate::vector::traits::Vector;
+pub use crate::matrix::matrix3::Mat3;
+pub use crate::matrix::matrix4::Mat4;
+pub use crate::geometry::aabb::Aabb;
+pub use crate::geometry::plane::Plane;
+pub use crate::geometry::ray::Ray;
+pub use crate::geometry::transform::Transform;
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::*;
+
+    #[test]
+    fn test_prelude_imports() {
+        let v2 = Vec2::new(1.0, 2.0);
+        let v3 = Vec3::new(1.0, 2.0, 3.0);
+        let v4 = Vec4::new(1.0, 2.0, 3.0, 4.0);
+        let q = Quaternion::new(v3, 1.0);
+
+        assert_eq!(v2 + v2, Vec2::new(2.0, 4.0));
+        assert_eq!(v3 - v3, Vec3::new(0.0, 0.0, 0.0));
+        assert_eq!(v4.to_pure_vec3(), v3);
+        assert_eq!(q.v, v3);
+        assert_eq!(Mat3::identity() * v3, v3);
+        assert_eq!(Mat4::identity() * v4, v4);
+        assert_eq!(Aabb::new(v3, v3).min, v3);
+        assert_eq!(Ray::new(v3, v3).origin, v3);
+        assert_eq!(Plane::from_point_normal(v3, v3).signed_distance(&v3), 0.0);
+        assert_eq!(Transform::identity().to_mat4(), Mat4::identity());
+        assert_eq!(Vec3::zero().get(0), 0.0);
+    }
+}
@@ -0,0 +1,35 @@
+// Shading helpers, Vec3 is used as an RGB color
+use crate::vector::vector3::Vec3;
+
+// Schlick approximation of the Fresnel reflectance for each channel
+// f0 is the reflectance at normal incidence, cos_theta is clamped to [0, 1]
+pub fn fresnel_schlick(cos_theta: f64, f0: Vec3) -> Vec3 {
+    let k = (1.0 - cos_theta.clamp(0.0, 1.0)).powi(5);
+    f0 + (Vec3::new(1.0, 1.0, 1.0) - f0) * k
+}
+
+#[cfg(test)]
+mod test {
+    use super::fresnel_schlick;
+    use crate::vector::vector3::Vec3;
+
+    #[test]
+    fn test_fresnel_normal_incidence() {
+        let f0 = Vec3::new(0.04, 0.5, 0.9);
+        assert_eq!(fresnel_schlick(1.0, f0), f0);
+    }
+
+    #[test]
+    fn test_fresnel_grazing() {
+        let f0 = Vec3::new(0.04, 0.5, 0.9);
+        assert_eq!(fresnel_schlick(0.0, f0), Vec3::new(1.0, 1.0, 1.0));
+        let r = fresnel_schlick(0.01, f0);
+        assert!(r.x > 0.9 && r.y > 0.9 && r.z > 0.9);
+    }
+
+    #[test]
+    fn test_fresnel_monotonic() {
+        let f0 = Vec3::new(0.04, 0.04, 0.04);
+        assert!(fresnel_schlick(0.2, f0).x > fresnel_schlick(0.8, f0).x);
+    }
+}
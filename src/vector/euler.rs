@@ -0,0 +1,112 @@
+// Accumulates incremental yaw / pitch / roll (e.g. mouse deltas) into a quaternion
+// Storing a quaternion instead of three angles avoids gimbal lock
+// Axis convention: yaw around +Y, pitch around +X, roll around +Z, angles in radians
+use super::vector3::Vec3;
+use super::quaternions::Quaternion;
+
+// Frame in which each increment is applied
+// Local: around the current axes of the object (flight like controls)
+// World: around the fixed world axes
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Frame {
+    Local,
+    World
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct EulerAccumulator {
+    orientation: Quaternion,
+    frame: Frame
+}
+
+impl EulerAccumulator {
+    pub fn new(frame: Frame) -> EulerAccumulator {
+        EulerAccumulator::from_orientation(Quaternion::identity(), frame)
+    }
+
+    pub fn from_orientation(orientation: Quaternion, frame: Frame) -> EulerAccumulator {
+        EulerAccumulator { orientation, frame }
+    }
+
+    pub fn yaw(&mut self, delta: f64) {
+        self.apply(Vec3::new(0.0, 1.0, 0.0), delta);
+    }
+
+    pub fn pitch(&mut self, delta: f64) {
+        self.apply(Vec3::new(1.0, 0.0, 0.0), delta);
+    }
+
+    pub fn roll(&mut self, delta: f64) {
+        self.apply(Vec3::new(0.0, 0.0, 1.0), delta);
+    }
+
+    pub fn orientation(&self) -> Quaternion {
+        self.orientation
+    }
+
+    pub fn frame(&self) -> Frame {
+        self.frame
+    }
+
+    fn apply(&mut self, axis: Vec3, delta: f64) {
+        let r = Quaternion::from_axis_angle(axis, delta);
+        self.orientation = match self.frame {
+            Frame::Local => self.orientation * r,
+            Frame::World => r * self.orientation
+        };
+        // Renormalize to stop the drift of many small increments
+        self.orientation.normalize();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{EulerAccumulator, Frame};
+    use crate::vector::vector3::Vec3;
+    use crate::vector::quaternions::Quaternion;
+
+    fn assert_quat_eq(a: Quaternion, b: Quaternion) {
+        assert_approx_eq::assert_approx_eq!(a.v.x, b.v.x, 0.000001);
+        assert_approx_eq::assert_approx_eq!(a.v.y, b.v.y, 0.000001);
+        assert_approx_eq::assert_approx_eq!(a.v.z, b.v.z, 0.000001);
+        assert_approx_eq::assert_approx_eq!(a.s, b.s, 0.000001);
+    }
+
+    #[test]
+    fn test_new() {
+        let acc = EulerAccumulator::new(Frame::Local);
+        assert_eq!(acc.orientation(), Quaternion::identity());
+        assert_eq!(acc.frame(), Frame::Local);
+    }
+
+    #[test]
+    fn test_yaw_then_pitch_local() {
+        let mut acc = EulerAccumulator::new(Frame::Local);
+        acc.yaw(0.5);
+        acc.pitch(0.3);
+        let yaw = Quaternion::from_axis_angle(Vec3::new(0.0, 1.0, 0.0), 0.5);
+        let pitch = Quaternion::from_axis_angle(Vec3::new(1.0, 0.0, 0.0), 0.3);
+        assert_quat_eq(acc.orientation(), yaw * pitch);
+    }
+
+    #[test]
+    fn test_yaw_then_pitch_world() {
+        let mut acc = EulerAccumulator::new(Frame::World);
+        acc.yaw(0.5);
+        acc.pitch(0.3);
+        let yaw = Quaternion::from_axis_angle(Vec3::new(0.0, 1.0, 0.0), 0.5);
+        let pitch = Quaternion::from_axis_angle(Vec3::new(1.0, 0.0, 0.0), 0.3);
+        assert_quat_eq(acc.orientation(), pitch * yaw);
+    }
+
+    #[test]
+    fn test_many_increments() {
+        let mut acc = EulerAccumulator::new(Frame::Local);
+        for _ in 0..1000 {
+            acc.roll(std::f64::consts::PI / 1000.0);
+        }
+        assert_approx_eq::assert_approx_eq!(acc.orientation().norm(), 1.0, 0.000001);
+        let r = acc.orientation().rotate_vector(&Vec3::new(1.0, 0.0, 0.0));
+        assert_approx_eq::assert_approx_eq!(r.x, -1.0, 0.000001);
+    }
+}
@@ -1,4 +1,6 @@
 pub mod vector2;
 pub mod vector3;
 pub mod vector4;
-pub mod quaternions;
\ No newline at end of file
+pub mod quaternions;
+pub mod euler;
+pub mod traits;
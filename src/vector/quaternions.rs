@@ -8,7 +8,10 @@ use std::ops::{
     Mul, MulAssign
 };
 use std::cmp::{PartialEq};
+use std::convert::TryInto;
 use super::vector3::Vec3;
+use crate::matrix::matrix3::Mat3;
+use crate::math::radians;
 
 #[derive(Clone, Copy, Debug)]
 pub struct Quaternion {
@@ -24,15 +27,90 @@ impl PartialEq for Quaternion {
 
 impl Quaternion {
     pub fn new(v: Vec3, s: f64) -> Quaternion {
-        Quaternion { v: v, s: s }
+        Quaternion { v, s }
+    }
+
+    pub fn identity() -> Quaternion {
+        Quaternion::new(Vec3::new(0.0, 0.0, 0.0), 1.0)
+    }
+
+    // Rotation of `angle` radians around `axis`
+    pub fn from_axis_angle(axis: Vec3, angle: f64) -> Quaternion {
+        let half = angle * 0.5;
+        Quaternion {
+            v: axis.get_normalize() * half.sin(),
+            s: half.cos()
+        }
+    }
+
+    // Shortest arc rotation turning the direction `from` onto `to`
+    // Opposite directions turn 180 degrees around an arbitrary perpendicular axis
+    pub fn from_rotation_arc(from: Vec3, to: Vec3) -> Quaternion {
+        let from = from.get_normalize();
+        let to = to.get_normalize();
+        let d = from.dot(&to);
+        if d < -1.0 + 1e-12 {
+            let (axis, _) = from.orthonormal_basis();
+            return Quaternion::new(axis, 0.0);
+        }
+        // Half way quaternion: (from x to, 1 + from . to) normalized
+        let mut q = Quaternion::new(from.cross(&to), 1.0 + d);
+        q.normalize();
+        q
+    }
+
+    // Euler angles in radians with the EulerAccumulator axes: roll around +Z,
+    // pitch around +X, yaw around +Y. Roll is applied first, then pitch,
+    // then yaw: q = yaw * pitch * roll
+    pub fn from_euler(roll: f64, pitch: f64, yaw: f64) -> Quaternion {
+        Quaternion::from_axis_angle(Vec3::new(0.0, 1.0, 0.0), yaw)
+            * Quaternion::from_axis_angle(Vec3::new(1.0, 0.0, 0.0), pitch)
+            * Quaternion::from_axis_angle(Vec3::new(0.0, 0.0, 1.0), roll)
+    }
+
+    // Inverse of from_axis_angle, returns a unit axis and an angle in radians
+    // A rotation close to the identity has no meaningful axis:
+    // it returns the x axis with an angle of 0
+    pub fn to_axis_angle(&self) -> (Vec3, f64) {
+        let mut q = self.copy();
+        q.normalize();
+
+        let sin_half = q.v.magnitude();
+        if sin_half < 1e-12 {
+            return (Vec3::new(1.0, 0.0, 0.0), 0.0);
+        }
+        (q.v * (1.0 / sin_half), 2.0 * sin_half.atan2(q.s))
     }
 
     pub fn copy(&self) -> Quaternion {
         Quaternion { v: self.v, s: self.s }
     }
 
+    // Little-endian wire format: v.x, v.y, v.z then s as f64
+    pub fn to_le_bytes(&self) -> [u8; 32] {
+        let mut r = [0; 32];
+        r[0..24].copy_from_slice(&self.v.to_le_bytes());
+        r[24..32].copy_from_slice(&self.s.to_le_bytes());
+        r
+    }
+
+    pub fn from_le_bytes(bytes: &[u8; 32]) -> Quaternion {
+        Quaternion {
+            v: Vec3::from_le_bytes(bytes[0..24].try_into().unwrap()),
+            s: f64::from_le_bytes(bytes[24..32].try_into().unwrap())
+        }
+    }
+
+    pub fn dot(&self, other: &Quaternion) -> f64 {
+        self.v.dot(&other.v) + self.s * other.s
+    }
+
+    pub fn norm_squared(&self) -> f64 {
+        self.s * self.s + self.v.dot(&self.v)
+    }
+
     pub fn norm(&self) -> f64 {
-        (self.s * self.s + self.v * self.v).sqrt()
+        self.norm_squared().sqrt()
     }
 
     pub fn normalize(&mut self) {
@@ -46,7 +124,7 @@ impl Quaternion {
 
     pub fn get_normliaze(&mut self) -> Quaternion {
         let norm = self.norm();
-        return if norm > 0.0 {
+        if norm > 0.0 {
             let nv = 1.0 / norm;
             Quaternion {
                 v: self.v * nv,
@@ -57,11 +135,13 @@ impl Quaternion {
         }
     }
 
+    // Interprets v as the rotation axis and s as an angle in degrees
     pub fn convert_to_unit_norm(&mut self) {
-        let angle = self.s * std::f64::consts::PI / 180.0;
-        self.normalize();
+        let angle = radians(self.s);
+        // Only the axis is normalized, the angle must not shrink it
+        self.v.normalize();
         self.s = (angle * 0.5).cos();
-        self.v = self.v * (angle * 0.5).sin();
+        self.v *= (angle * 0.5).sin();
     }
 
     pub fn conjugate(&self) -> Quaternion {
@@ -71,13 +151,9 @@ impl Quaternion {
         }
     }
 
+    // q^-1 = q* / |q|^2
     pub fn inverse(&self) -> Quaternion {
-        let norm = self.norm();
-        let conj = self.conjugate();
-        Quaternion {
-            v: conj.v * (1.0 / (norm * norm)),
-            s: conj.s * norm
-        }
+        self.conjugate() * (1.0 / self.norm_squared())
     }
 
     pub fn rotate(&self, rhs: &Quaternion) -> Vec3 {
@@ -85,7 +161,204 @@ impl Quaternion {
 
         q.v.normalize();
         q.convert_to_unit_norm();
-        return (q * *self * q.inverse()).v;
+        (q * *self * q.inverse()).v
+    }
+
+    // Rotates v by self, self must already be a unit rotation quaternion
+    // (e.g. built with from_axis_angle): q * (0, v) * q*
+    pub fn rotate_vector(&self, v: &Vec3) -> Vec3 {
+        (*self * Quaternion::new(*v, 0.0) * self.conjugate()).v
+    }
+
+    // Rotates every point in place, the rotation matrix is built once so
+    // each point costs a 3x3 product instead of two quaternion products
+    pub fn rotate_slice(&self, points: &mut [Vec3]) {
+        let m = Mat3::from_quaternion(*self);
+        for p in points.iter_mut() {
+            *p = m * *p;
+        }
+    }
+
+    // Exponential map: a pure quaternion (axis * half angle, 0) gives the
+    // unit rotation, a non zero scalar part scales the result by e^s
+    pub fn exp(&self) -> Quaternion {
+        let angle = self.v.magnitude();
+        let scale = self.s.exp();
+        // sin(x) / x tends to 1 near the identity
+        let sinc = if angle < 1e-9 { 1.0 } else { angle.sin() / angle };
+        Quaternion::new(self.v * (scale * sinc), scale * angle.cos())
+    }
+
+    // Logarithm map, inverse of exp: (axis * half angle, ln |q|)
+    pub fn ln(&self) -> Quaternion {
+        let sin_half = self.v.magnitude();
+        let half = sin_half.atan2(self.s);
+        let v = if sin_half < 1e-9 && self.s > 0.0 {
+            // atan2(x, s) / x tends to 1 / s near the identity
+            self.v / self.s
+        } else if sin_half > 0.0 {
+            self.v * (half / sin_half)
+        } else if self.s < 0.0 {
+            // A negative real is a half turn about any axis, x is picked
+            Vec3::new(half, 0.0, 0.0)
+        } else {
+            // Zero quaternion, only the scalar part diverges
+            self.v
+        };
+        Quaternion::new(v, self.norm().ln())
+    }
+
+    // Rotation scaled by t: exp(t * ln(self))
+    pub fn pow(&self, t: f64) -> Quaternion {
+        (self.ln() * t).exp()
+    }
+
+    // Smallest angle (radians, in [0, PI]) of the rotation from self to other
+    // Both must be unit quaternions, q and -q are the same orientation
+    pub fn angle_to(&self, other: &Quaternion) -> f64 {
+        2.0 * self.dot(other).abs().min(1.0).acos()
+    }
+
+    // Weighted average of rotations: each quaternion is flipped onto the
+    // hemisphere of the first one, weight-summed, then normalized
+    // A good approximation when the rotations are close to each other
+    // Extra quaternions or weights are ignored, an empty input gives the identity
+    pub fn weighted_average(quats: &[Quaternion], weights: &[f64]) -> Quaternion {
+        let first = match quats.first() {
+            Some(q) => *q,
+            None => return Quaternion::identity()
+        };
+        let mut sum = Quaternion::new(Vec3::new(0.0, 0.0, 0.0), 0.0);
+        for (q, &w) in quats.iter().zip(weights.iter()) {
+            let sign = if q.dot(&first) < 0.0 { -1.0 } else { 1.0 };
+            sum += *q * (w * sign);
+        }
+        if sum.norm_squared() == 0.0 {
+            return Quaternion::identity();
+        }
+        sum.normalize();
+        sum
+    }
+
+    // Normalized linear interpolation, takes the shortest path
+    // Cheaper than a slerp but the angular velocity is not constant
+    pub fn nlerp(&self, other: &Quaternion, t: f64) -> Quaternion {
+        let other = if self.dot(other) < 0.0 {
+            *other * -1.0
+        } else {
+            *other
+        };
+        let mut r = *self + (other - *self) * t;
+        r.normalize();
+        r
+    }
+
+    // Spherical linear interpolation along the shortest path, constant angular velocity
+    // Both inputs are normalized first so the result is always unit length
+    pub fn slerp(&self, other: &Quaternion, t: f64) -> Quaternion {
+        let mut a = *self;
+        let mut b = *other;
+        a.normalize();
+        b.normalize();
+        a.slerp_unnormalized(&b, t)
+    }
+
+    // slerp without the normalization, self and other must be unit quaternions
+    pub fn slerp_unnormalized(&self, other: &Quaternion, t: f64) -> Quaternion {
+        let mut d = self.dot(other);
+        let other = if d < 0.0 {
+            d = -d;
+            *other * -1.0
+        } else {
+            *other
+        };
+        // Nearly identical rotations, sin(theta) would be close to 0
+        if d > 0.9995 {
+            return self.nlerp(&other, t);
+        }
+        let theta = d.min(1.0).acos();
+        let sin_theta = theta.sin();
+        *self * (((1.0 - t) * theta).sin() / sin_theta) + other * ((t * theta).sin() / sin_theta)
+    }
+
+    // Limits the pitch of a camera, the angle (radians) between its forward
+    // axis (local -Z) and the horizontal plane defined by `up`
+    // The correction is done around the horizontal axis so the yaw is kept
+    pub fn clamp_pitch(&self, min: f64, max: f64, up: Vec3) -> Quaternion {
+        let up = up.get_normalize();
+        let forward = self.rotate_vector(&Vec3::new(0.0, 0.0, -1.0)).get_normalize();
+        let pitch = forward.dot(&up).clamp(-1.0, 1.0).asin();
+        let clamped = pitch.clamp(min, max);
+        if clamped == pitch {
+            return *self;
+        }
+
+        let mut axis = forward.cross(&up);
+        if axis.magnitude() < 1e-9 {
+            // Looking straight up or down, the local right axis is horizontal
+            axis = self.rotate_vector(&Vec3::new(1.0, 0.0, 0.0));
+        }
+        let mut r = Quaternion::from_axis_angle(axis, clamped - pitch) * *self;
+        r.normalize();
+        r
+    }
+
+    // Rotation turning the local -Z axis onto `forward` with local +Y as close
+    // as possible to `up`, the usual camera convention
+    // If forward and up are parallel any perpendicular up is picked
+    pub fn look_rotation(forward: Vec3, up: Vec3) -> Quaternion {
+        let back = -forward.get_normalize();
+        let right = match up.cross(&back).try_normalize() {
+            Some(right) => right,
+            None => back.orthonormal_basis().0
+        };
+        let up = back.cross(&right);
+        Mat3::new([
+            [right.x, up.x, back.x],
+            [right.y, up.y, back.y],
+            [right.z, up.z, back.z]
+        ]).to_quaternion()
+    }
+
+    // Smallest world space rotation bringing the local up (+Y) onto world_up
+    // Apply it as `self.upright(up) * self`, the heading is left untouched
+    pub fn upright(&self, world_up: Vec3) -> Quaternion {
+        let world_up = world_up.get_normalize();
+        let up = self.rotate_vector(&Vec3::new(0.0, 1.0, 0.0)).get_normalize();
+        let angle = up.dot(&world_up).clamp(-1.0, 1.0).acos();
+        if angle < 1e-9 {
+            return Quaternion::identity();
+        }
+
+        let mut axis = up.cross(&world_up);
+        if axis.magnitude() < 1e-9 {
+            // Upside down, flip around the local right axis
+            axis = self.rotate_vector(&Vec3::new(1.0, 0.0, 0.0));
+        }
+        Quaternion::from_axis_angle(axis, angle)
+    }
+
+    // One step of a damped torsional spring pulling `current` toward `target`
+    // The error is the box-minus target [-] current: the world space rotation
+    // vector (axis * angle) of the shortest rotation from current to target
+    // Integrated with semi-implicit Euler, returns (orientation, angular velocity)
+    pub fn spring(current: Quaternion, ang_vel: Vec3, target: Quaternion,
+        stiffness: f64, damping: f64, dt: f64) -> (Quaternion, Vec3) {
+        let mut delta = target * current.conjugate();
+        if delta.s < 0.0 {
+            delta *= -1.0;
+        }
+        let (axis, angle) = delta.to_axis_angle();
+        let error = axis * angle;
+
+        let ang_vel = ang_vel + (error * stiffness - ang_vel * damping) * dt;
+        let step = ang_vel.magnitude() * dt;
+        if step == 0.0 {
+            return (current, ang_vel);
+        }
+        let mut r = Quaternion::from_axis_angle(ang_vel, step) * current;
+        r.normalize();
+        (r, ang_vel)
     }
 }
 
@@ -143,6 +416,15 @@ impl MulAssign<Quaternion> for Quaternion {
     }
 }
 
+// Rotation of a vector, shorthand for rotate_vector
+impl Mul<Vec3> for Quaternion {
+    type Output = Vec3;
+
+    fn mul(self, v: Vec3) -> Vec3 {
+        self.rotate_vector(&v)
+    }
+}
+
 // Implementation of scalar multiplication
 impl Mul<f64> for Quaternion {
     type Output = Quaternion;
@@ -161,3 +443,487 @@ impl MulAssign<f64> for Quaternion {
         self.s *= other;
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::Quaternion;
+    use crate::vector::vector3::Vec3;
+    use std::f64::consts::PI;
+
+    fn assert_vec3_eq(a: Vec3, b: Vec3) {
+        assert_approx_eq::assert_approx_eq!(a.x, b.x, 0.000001);
+        assert_approx_eq::assert_approx_eq!(a.y, b.y, 0.000001);
+        assert_approx_eq::assert_approx_eq!(a.z, b.z, 0.000001);
+    }
+
+    #[test]
+    fn test_from_axis_angle() {
+        let q = Quaternion::from_axis_angle(Vec3::new(0.0, 0.0, 2.0), PI);
+        assert_vec3_eq(q.v, Vec3::new(0.0, 0.0, 1.0));
+        assert_approx_eq::assert_approx_eq!(q.s, 0.0, 0.000001);
+        assert_approx_eq::assert_approx_eq!(q.norm(), 1.0, 0.000001);
+    }
+
+    #[test]
+    fn test_rotate_vector_x() {
+        let q = Quaternion::from_axis_angle(Vec3::new(1.0, 0.0, 0.0), PI / 2.0);
+        assert_vec3_eq(q.rotate_vector(&Vec3::new(0.0, 1.0, 0.0)), Vec3::new(0.0, 0.0, 1.0));
+        assert_vec3_eq(q.rotate_vector(&Vec3::new(1.0, 0.0, 0.0)), Vec3::new(1.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_rotate_vector_y() {
+        let q = Quaternion::from_axis_angle(Vec3::new(0.0, 1.0, 0.0), PI / 2.0);
+        assert_vec3_eq(q.rotate_vector(&Vec3::new(0.0, 0.0, 1.0)), Vec3::new(1.0, 0.0, 0.0));
+        assert_vec3_eq(q.rotate_vector(&Vec3::new(0.0, 1.0, 0.0)), Vec3::new(0.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn test_rotate_vector_z() {
+        let q = Quaternion::from_axis_angle(Vec3::new(0.0, 0.0, 1.0), PI / 2.0);
+        assert_vec3_eq(q.rotate_vector(&Vec3::new(1.0, 0.0, 0.0)), Vec3::new(0.0, 1.0, 0.0));
+        assert_vec3_eq(q.rotate_vector(&Vec3::new(0.0, 0.0, 1.0)), Vec3::new(0.0, 0.0, 1.0));
+    }
+
+    #[test]
+    fn test_rotate() {
+        let p = Quaternion::new(Vec3::new(1.0, 0.0, 0.0), 0.0);
+        let r = p.rotate(&Quaternion::new(Vec3::new(0.0, 0.0, 1.0), 90.0));
+        assert_vec3_eq(r, Vec3::new(0.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn test_to_axis_angle_round_trip() {
+        let axes = [
+            Vec3::new(1.0, 0.0, 0.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            Vec3::new(0.0, 0.0, 1.0),
+            Vec3::new(1.0, -2.0, 0.5).get_normalize()
+        ];
+        let angles = [0.1, PI / 4.0, PI / 2.0, 2.0, PI - 0.01];
+
+        for axis in axes.iter() {
+            for angle in angles.iter() {
+                let (a, t) = Quaternion::from_axis_angle(*axis, *angle).to_axis_angle();
+                assert_vec3_eq(a, *axis);
+                assert_approx_eq::assert_approx_eq!(t, *angle, 0.000001);
+            }
+        }
+    }
+
+    #[test]
+    fn test_to_axis_angle_identity() {
+        let q = Quaternion::new(Vec3::new(0.0, 0.0, 0.0), 1.0);
+        assert_eq!(q.to_axis_angle(), (Vec3::new(1.0, 0.0, 0.0), 0.0));
+    }
+
+    fn pitch_of(q: &Quaternion) -> f64 {
+        q.rotate_vector(&Vec3::new(0.0, 0.0, -1.0)).y.asin()
+    }
+
+    #[test]
+    fn test_clamp_pitch_clamped() {
+        let up = Vec3::new(0.0, 1.0, 0.0);
+        let yaw = Quaternion::from_axis_angle(up, 0.4);
+        let pitch = Quaternion::from_axis_angle(Vec3::new(1.0, 0.0, 0.0), 1.3);
+        let q = yaw * pitch;
+        assert_approx_eq::assert_approx_eq!(pitch_of(&q), 1.3, 0.000001);
+
+        let r = q.clamp_pitch(-1.0, 1.0, up);
+        assert_approx_eq::assert_approx_eq!(pitch_of(&r), 1.0, 0.000001);
+        assert_approx_eq::assert_approx_eq!(r.norm(), 1.0, 0.000001);
+
+        // Same heading on the horizontal plane
+        let heading = |q: &Quaternion| {
+            let f = q.rotate_vector(&Vec3::new(0.0, 0.0, -1.0));
+            f.x.atan2(-f.z)
+        };
+        assert_approx_eq::assert_approx_eq!(heading(&r), heading(&q), 0.000001);
+
+        let down = yaw * Quaternion::from_axis_angle(Vec3::new(1.0, 0.0, 0.0), -1.5);
+        assert_approx_eq::assert_approx_eq!(pitch_of(&down.clamp_pitch(-1.0, 1.0, up)), -1.0, 0.000001);
+    }
+
+    #[test]
+    fn test_clamp_pitch_in_range() {
+        let up = Vec3::new(0.0, 1.0, 0.0);
+        let q = Quaternion::from_axis_angle(up, 0.4)
+            * Quaternion::from_axis_angle(Vec3::new(1.0, 0.0, 0.0), 0.5);
+        assert_eq!(q.clamp_pitch(-1.0, 1.0, up), q);
+    }
+
+    #[test]
+    fn test_clamp_pitch_vertical() {
+        let up = Vec3::new(0.0, 1.0, 0.0);
+        let q = Quaternion::from_axis_angle(Vec3::new(1.0, 0.0, 0.0), PI / 2.0);
+        assert_approx_eq::assert_approx_eq!(pitch_of(&q.clamp_pitch(-1.0, 1.0, up)), 1.0, 0.000001);
+    }
+
+    #[test]
+    fn test_le_bytes() {
+        let q = Quaternion::from_axis_angle(Vec3::new(1.0, 2.0, 3.0), 0.3);
+        let bytes = q.to_le_bytes();
+        assert_eq!(bytes.len(), 32);
+        assert_eq!(bytes[24..32], q.s.to_le_bytes());
+        assert_eq!(Quaternion::from_le_bytes(&bytes), q);
+    }
+
+    fn assert_quat_eq(a: Quaternion, b: Quaternion) {
+        assert_vec3_eq(a.v, b.v);
+        assert_approx_eq::assert_approx_eq!(a.s, b.s, 0.000001);
+    }
+
+    #[test]
+    fn test_dot() {
+        let a = Quaternion::new(Vec3::new(1.0, 2.0, 3.0), 4.0);
+        let b = Quaternion::new(Vec3::new(-1.0, 0.5, 2.0), 0.5);
+        assert_eq!(a.dot(&b), -1.0 + 1.0 + 6.0 + 2.0);
+    }
+
+    #[test]
+    fn test_nlerp() {
+        let a = Quaternion::from_axis_angle(Vec3::new(0.0, 0.0, 1.0), 0.2);
+        let b = Quaternion::from_axis_angle(Vec3::new(0.0, 0.0, 1.0), 1.4);
+        assert_quat_eq(a.nlerp(&b, 0.0), a);
+        assert_quat_eq(a.nlerp(&b, 1.0), b);
+        let mid = a.nlerp(&b, 0.5);
+        assert_approx_eq::assert_approx_eq!(mid.norm(), 1.0, 0.000001);
+        assert_quat_eq(mid, Quaternion::from_axis_angle(Vec3::new(0.0, 0.0, 1.0), 0.8));
+    }
+
+    #[test]
+    fn test_nlerp_shortest_path() {
+        let a = Quaternion::from_axis_angle(Vec3::new(1.0, 0.0, 0.0), 0.3);
+        let b = Quaternion::from_axis_angle(Vec3::new(1.0, 0.0, 0.0), 0.9) * -1.0;
+        let r = a.nlerp(&b, 0.5);
+        assert_approx_eq::assert_approx_eq!(r.norm(), 1.0, 0.000001);
+        assert_quat_eq(r, Quaternion::from_axis_angle(Vec3::new(1.0, 0.0, 0.0), 0.6));
+    }
+
+    #[test]
+    fn test_norm_squared() {
+        let q = Quaternion::new(Vec3::new(1.0, 2.0, 3.0), 4.0);
+        assert_eq!(q.norm_squared(), 30.0);
+        assert_eq!(q.norm(), 30.0_f64.sqrt());
+    }
+
+    #[test]
+    fn test_inverse() {
+        let q = Quaternion::new(Vec3::new(1.0, 2.0, 3.0), 4.0);
+        assert_quat_eq(q * q.inverse(), Quaternion::identity());
+        assert_quat_eq(q.inverse() * q, Quaternion::identity());
+    }
+
+    #[test]
+    fn test_conjugate_is_inverse_for_unit() {
+        let q = Quaternion::from_axis_angle(Vec3::new(-1.0, 0.5, 2.0), 2.2);
+        assert_quat_eq(q.conjugate(), q.inverse());
+        assert_quat_eq(q * q.conjugate(), Quaternion::identity());
+    }
+
+    #[test]
+    fn test_inverse_non_unit() {
+        let q = Quaternion::from_axis_angle(Vec3::new(0.0, 1.0, 0.0), 0.9) * 2.5;
+        assert_quat_eq(q * q.inverse(), Quaternion::identity());
+        assert_quat_eq(q.inverse() * q, Quaternion::identity());
+        // q * q* is the squared norm, so the conjugate alone is not the inverse
+        assert_quat_eq(q * q.conjugate(), Quaternion::new(Vec3::new(0.0, 0.0, 0.0), 6.25));
+        assert_quat_eq(q.inverse(), q.conjugate() * (1.0 / 6.25));
+    }
+
+    #[test]
+    fn test_upright() {
+        let up = Vec3::new(0.0, 1.0, 0.0);
+        let yawed = Quaternion::from_axis_angle(up, 0.7);
+        assert_quat_eq(yawed.upright(up), Quaternion::identity());
+
+        let tilted = Quaternion::from_axis_angle(Vec3::new(1.0, 0.0, 1.0), 0.5) * yawed;
+        let fixed = tilted.upright(up) * tilted;
+        assert_vec3_eq(fixed.rotate_vector(&up), up);
+
+        let upside_down = Quaternion::from_axis_angle(Vec3::new(0.0, 0.0, 1.0), PI);
+        let fixed = upside_down.upright(up) * upside_down;
+        assert_vec3_eq(fixed.rotate_vector(&up), up);
+
+        // Righting a vehicle on a tilted world up
+        let world_up = Vec3::new(1.0, 1.0, 0.0).get_normalize();
+        let fixed = yawed.upright(world_up) * yawed;
+        assert_vec3_eq(fixed.rotate_vector(&up), world_up);
+    }
+
+    #[test]
+    fn test_look_rotation() {
+        use crate::matrix::matrix3::Mat3;
+
+        let up = Vec3::new(0.0, 1.0, 0.0);
+        assert_quat_eq(Quaternion::look_rotation(Vec3::new(0.0, 0.0, -1.0), up), Quaternion::identity());
+
+        let forward = Vec3::new(1.0, -0.5, 2.0);
+        let q = Quaternion::look_rotation(forward, up);
+        let f = forward.get_normalize();
+        assert_vec3_eq(q.rotate_vector(&Vec3::new(0.0, 0.0, -1.0)), f);
+
+        // Same basis built as a look matrix: columns are right, up and back
+        let right = f.cross(&up).get_normalize();
+        let true_up = right.cross(&f);
+        let look = Mat3::new([
+            [right.x, true_up.x, -f.x],
+            [right.y, true_up.y, -f.y],
+            [right.z, true_up.z, -f.z]
+        ]);
+        let r = Mat3::from_quaternion(q);
+        for i in 0..3 {
+            for j in 0..3 {
+                assert_approx_eq::assert_approx_eq!(r.m[i][j], look.m[i][j], 0.000001);
+            }
+        }
+
+        // Parallel forward and up still gives a valid rotation
+        let q = Quaternion::look_rotation(up * 3.0, up);
+        assert_approx_eq::assert_approx_eq!(q.norm(), 1.0, 0.000001);
+        assert_vec3_eq(q.rotate_vector(&Vec3::new(0.0, 0.0, -1.0)), up);
+    }
+
+    #[test]
+    fn test_spring() {
+        let target = Quaternion::from_axis_angle(Vec3::new(0.0, 1.0, 0.0), 0.3);
+        let start = Quaternion::from_axis_angle(Vec3::new(1.0, 1.0, -0.5), 2.5);
+        let angle_to_target = |q: Quaternion| (target * q.conjugate()).to_axis_angle().1;
+
+        let stiffness: f64 = 40.0;
+        let damping = 2.0 * stiffness.sqrt();
+        let (mut q, mut w) = (start, Vec3::new(0.0, 0.0, 0.0));
+        let initial = angle_to_target(start);
+        for _ in 0..600 {
+            let (nq, nw) = Quaternion::spring(q, w, target, stiffness, damping, 1.0 / 60.0);
+            q = nq;
+            w = nw;
+            assert!(angle_to_target(q) <= initial + 0.000001);
+        }
+        assert!(angle_to_target(q) < 0.001);
+        assert!(w.magnitude() < 0.001);
+        assert_approx_eq::assert_approx_eq!(q.norm(), 1.0, 0.000001);
+
+        // At rest on the target nothing moves
+        let (q, w) = Quaternion::spring(target, Vec3::new(0.0, 0.0, 0.0), target, stiffness, damping, 0.1);
+        assert_quat_eq(q, target);
+        assert_eq!(w, Vec3::new(0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_exp_ln() {
+        let q = Quaternion::from_axis_angle(Vec3::new(1.0, 2.0, -1.0), 1.2);
+        let l = q.ln();
+        assert_approx_eq::assert_approx_eq!(l.s, 0.0, 0.000001);
+        assert_approx_eq::assert_approx_eq!(l.v.magnitude(), 0.6, 0.000001);
+        assert_quat_eq(l.exp(), q);
+
+        assert_quat_eq(Quaternion::identity().ln(), Quaternion::new(Vec3::new(0.0, 0.0, 0.0), 0.0));
+        assert_quat_eq(Quaternion::new(Vec3::new(0.0, 0.0, 0.0), 0.0).exp(), Quaternion::identity());
+    }
+
+    #[test]
+    fn test_pow() {
+        let q = Quaternion::from_axis_angle(Vec3::new(0.0, 1.0, 1.0), 2.0);
+        assert_quat_eq(q.pow(1.0), q);
+        assert_quat_eq(q.pow(0.0), Quaternion::identity());
+        assert_quat_eq(q.pow(0.5), Quaternion::from_axis_angle(Vec3::new(0.0, 1.0, 1.0), 1.0));
+
+        let r = q.pow(0.5).pow(2.0);
+        let sign = if r.dot(&q) < 0.0 { -1.0 } else { 1.0 };
+        assert_quat_eq(r * sign, q);
+
+        // Tiny rotations stay stable
+        let tiny = Quaternion::from_axis_angle(Vec3::new(1.0, 0.0, 0.0), 1e-12);
+        assert_approx_eq::assert_approx_eq!(tiny.pow(3.0).norm(), 1.0, 0.000001);
+    }
+
+    #[test]
+    fn test_pow_near_minus_one() {
+        // A full turn about x, the half power is a half turn
+        let q = Quaternion::new(Vec3::new(1e-12, 0.0, 0.0), -1.0);
+        let h = q.pow(0.5);
+        assert_approx_eq::assert_approx_eq!(h.s, 0.0, 0.000001);
+        assert_approx_eq::assert_approx_eq!(h.v.x, 1.0, 0.000001);
+
+        let minus_one = Quaternion::new(Vec3::new(0.0, 0.0, 0.0), -1.0);
+        assert_quat_eq(minus_one.pow(0.5), Quaternion::new(Vec3::new(1.0, 0.0, 0.0), 0.0));
+        assert_quat_eq(minus_one.pow(2.0), Quaternion::identity());
+    }
+
+    #[test]
+    fn test_ln_zero() {
+        let l = Quaternion::new(Vec3::new(0.0, 0.0, 0.0), 0.0).ln();
+        assert_eq!(l.v, Vec3::new(0.0, 0.0, 0.0));
+        assert_eq!(l.s, f64::NEG_INFINITY);
+    }
+
+    // Right-handed 90 degree rotations of every basis vector about every axis
+    fn quarter_turns() -> Vec<(Vec3, Vec3, Vec3)> {
+        let x = Vec3::new(1.0, 0.0, 0.0);
+        let y = Vec3::new(0.0, 1.0, 0.0);
+        let z = Vec3::new(0.0, 0.0, 1.0);
+        vec![
+            // (axis, input, expected)
+            (x, x, x), (x, y, z), (x, z, -y),
+            (y, x, -z), (y, y, y), (y, z, x),
+            (z, x, y), (z, y, -x), (z, z, z)
+        ]
+    }
+
+    #[test]
+    fn test_rotation_harness_rotate_vector() {
+        for (axis, input, expected) in quarter_turns() {
+            let q = Quaternion::from_axis_angle(axis, PI / 2.0);
+            assert_vec3_eq(q.rotate_vector(&input), expected);
+            // The inverse rotation undoes it
+            assert_vec3_eq(q.inverse().rotate_vector(&expected), input);
+            // Negative angle goes the other way
+            let back = Quaternion::from_axis_angle(axis, -PI / 2.0);
+            assert_vec3_eq(back.rotate_vector(&expected), input);
+        }
+    }
+
+    #[test]
+    fn test_rotation_harness_legacy_rotate() {
+        for (axis, input, expected) in quarter_turns() {
+            let from_degrees = Quaternion::new(input, 0.0).rotate(&Quaternion::new(axis, 90.0));
+            assert_vec3_eq(from_degrees, expected);
+            assert_vec3_eq(from_degrees, Quaternion::from_axis_angle(axis, PI / 2.0).rotate_vector(&input));
+        }
+    }
+
+    #[test]
+    fn test_rotation_harness_composition() {
+        // b * a applies a first, then b
+        let a = Quaternion::from_axis_angle(Vec3::new(0.0, 0.0, 1.0), PI / 2.0);
+        let b = Quaternion::from_axis_angle(Vec3::new(1.0, 0.0, 0.0), PI / 2.0);
+        let v = Vec3::new(1.0, 0.0, 0.0);
+        assert_vec3_eq((b * a).rotate_vector(&v), b.rotate_vector(&a.rotate_vector(&v)));
+        assert_vec3_eq((b * a).rotate_vector(&v), Vec3::new(0.0, 0.0, 1.0));
+        assert_vec3_eq((a * b).rotate_vector(&v), Vec3::new(0.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn test_angle_to() {
+        let axis = Vec3::new(1.0, -1.0, 2.0);
+        let q = Quaternion::from_axis_angle(axis, 0.4);
+        assert_approx_eq::assert_approx_eq!(q.angle_to(&q), 0.0, 0.000001);
+        assert_approx_eq::assert_approx_eq!(q.angle_to(&(q * -1.0)), 0.0, 0.000001);
+
+        let r = Quaternion::from_axis_angle(axis, 0.4 + PI / 2.0);
+        assert_approx_eq::assert_approx_eq!(q.angle_to(&r), PI / 2.0, 0.000001);
+        assert_approx_eq::assert_approx_eq!(r.angle_to(&q), PI / 2.0, 0.000001);
+
+        // 270 degrees one way is 90 degrees the other way
+        let z = Vec3::new(0.0, 0.0, 1.0);
+        let far = Quaternion::from_axis_angle(z, 1.5 * PI);
+        assert_approx_eq::assert_approx_eq!(Quaternion::identity().angle_to(&far), PI / 2.0, 0.000001);
+    }
+
+    #[test]
+    fn test_mul_vec3() {
+        let q = Quaternion::from_axis_angle(Vec3::new(0.0, 0.0, 1.0), PI / 2.0);
+        let x = Vec3::new(1.0, 0.0, 0.0);
+        assert_vec3_eq(q * x, Vec3::new(0.0, 1.0, 0.0));
+        assert_eq!(q * x, q.rotate_vector(&x));
+
+        let v = Vec3::new(1.0, 2.0, 3.0);
+        assert_vec3_eq(q * (q * v), (q * q) * v);
+    }
+
+    #[test]
+    fn test_weighted_average() {
+        let axis = Vec3::new(0.0, 1.0, 0.0);
+        let a = Quaternion::from_axis_angle(axis, 0.2);
+        let b = Quaternion::from_axis_angle(axis, 0.4);
+
+        let avg = Quaternion::weighted_average(&[a, b], &[0.5, 0.5]);
+        assert_approx_eq::assert_approx_eq!(avg.norm(), 1.0, 0.000001);
+        assert_approx_eq::assert_approx_eq!(avg.angle_to(&a), 0.1, 0.000001);
+        assert_approx_eq::assert_approx_eq!(avg.angle_to(&b), 0.1, 0.000001);
+
+        // Heavier weight pulls toward b, sign flips are handled
+        let avg = Quaternion::weighted_average(&[a, b * -1.0], &[0.25, 0.75]);
+        assert!(avg.angle_to(&b) < avg.angle_to(&a));
+        assert!(avg.angle_to(&a) < a.angle_to(&b));
+
+        assert_quat_eq(Quaternion::weighted_average(&[], &[]), Quaternion::identity());
+        assert_quat_eq(Quaternion::weighted_average(&[b], &[2.0]), b);
+    }
+
+    #[test]
+    fn test_from_euler() {
+        use crate::vector::euler::{EulerAccumulator, Frame};
+
+        let mut acc = EulerAccumulator::new(Frame::Local);
+        acc.yaw(0.8);
+        acc.pitch(-0.3);
+        acc.roll(1.1);
+        assert_quat_eq(Quaternion::from_euler(1.1, -0.3, 0.8), acc.orientation());
+        assert_quat_eq(Quaternion::from_euler(0.0, 0.0, 0.0), Quaternion::identity());
+    }
+
+    #[test]
+    fn test_from_rotation_arc() {
+        let x = Vec3::new(1.0, 0.0, 0.0);
+        let y = Vec3::new(0.0, 1.0, 0.0);
+        let q = Quaternion::from_rotation_arc(x, y * 4.0);
+        assert_quat_eq(q, Quaternion::from_axis_angle(Vec3::new(0.0, 0.0, 1.0), PI / 2.0));
+
+        let q = Quaternion::from_rotation_arc(x, -x);
+        assert_approx_eq::assert_approx_eq!(q.norm(), 1.0, 0.000001);
+        assert_approx_eq::assert_approx_eq!(q.angle_to(&Quaternion::identity()), PI, 0.000001);
+        assert_vec3_eq(q * x, -x);
+
+        assert_quat_eq(Quaternion::from_rotation_arc(y, y), Quaternion::identity());
+
+        let from = Vec3::new(1.0, 2.0, -0.5);
+        let to = Vec3::new(-3.0, 0.1, 2.0);
+        let q = Quaternion::from_rotation_arc(from, to);
+        assert_vec3_eq(q * from.get_normalize(), to.get_normalize());
+    }
+
+    #[test]
+    fn test_rotate_slice() {
+        let q = Quaternion::from_axis_angle(Vec3::new(1.0, -2.0, 0.5), 1.3);
+        let original: Vec<Vec3> = (0..7).map(|i| {
+            let t = i as f64;
+            Vec3::new(t, 1.0 - t, t * t)
+        }).collect();
+        let mut points = original.clone();
+        q.rotate_slice(&mut points);
+        for (p, o) in points.iter().zip(original.iter()) {
+            assert_vec3_eq(*p, q.rotate_vector(o));
+        }
+    }
+
+    #[test]
+    fn test_slerp() {
+        let axis = Vec3::new(0.0, 0.0, 1.0);
+        let a = Quaternion::from_axis_angle(axis, 0.0);
+        let b = Quaternion::from_axis_angle(axis, 2.0);
+        assert_quat_eq(a.slerp(&b, 0.0), a);
+        assert_quat_eq(a.slerp(&b, 1.0), b);
+        // Constant angular velocity, unlike nlerp
+        assert_quat_eq(a.slerp(&b, 0.25), Quaternion::from_axis_angle(axis, 0.5));
+        assert_quat_eq(a.slerp_unnormalized(&b, 0.25), Quaternion::from_axis_angle(axis, 0.5));
+
+        // Shortest path even when the signs differ
+        assert_quat_eq(a.slerp(&(b * -1.0), 0.5), Quaternion::from_axis_angle(axis, 1.0));
+
+        // Nearly identical inputs stay stable
+        let c = Quaternion::from_axis_angle(axis, 1e-6);
+        assert_approx_eq::assert_approx_eq!(a.slerp(&c, 0.5).norm(), 1.0, 0.000001);
+    }
+
+    #[test]
+    fn test_slerp_non_unit() {
+        let axis = Vec3::new(1.0, 1.0, 0.0);
+        let a = Quaternion::from_axis_angle(axis, 0.3);
+        let b = Quaternion::from_axis_angle(axis, 1.9);
+        let r = (a * 3.0).slerp(&(b * 0.2), 0.4);
+        assert_approx_eq::assert_approx_eq!(r.norm(), 1.0, 0.000001);
+        assert_quat_eq(r, a.slerp(&b, 0.4));
+    }
+}
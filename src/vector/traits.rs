@@ -0,0 +1,167 @@
+// Common interface of Vec2, Vec3 and Vec4 for dimension generic code
+// Components are indexed from 0 (x) to DIM - 1
+use std::ops::{
+    Add,
+    Sub,
+    Mul
+};
+use super::vector2::Vec2;
+use super::vector3::Vec3;
+use super::vector4::Vec4;
+
+pub trait Vector:
+    Copy + Add<Output = Self> + Sub<Output = Self> + Mul<f64, Output = Self>
+{
+    const DIM: usize;
+
+    fn zero() -> Self;
+
+    // Panics if i >= DIM
+    fn get(&self, i: usize) -> f64;
+
+    // Panics if i >= DIM
+    fn set(&mut self, i: usize, v: f64);
+
+    fn dot(&self, other: &Self) -> f64 {
+        (0..Self::DIM).map(|i| self.get(i) * other.get(i)).sum()
+    }
+
+    // Length over every component (w included for Vec4)
+    fn magnitude(&self) -> f64 {
+        Vector::dot(self, self).sqrt()
+    }
+}
+
+impl Vector for Vec2 {
+    const DIM: usize = 2;
+
+    fn zero() -> Vec2 {
+        Vec2::new(0.0, 0.0)
+    }
+
+    fn get(&self, i: usize) -> f64 {
+        match i {
+            0 => self.x,
+            1 => self.y,
+            _ => panic!("Vec2 index out of range: {}", i)
+        }
+    }
+
+    fn set(&mut self, i: usize, v: f64) {
+        match i {
+            0 => self.x = v,
+            1 => self.y = v,
+            _ => panic!("Vec2 index out of range: {}", i)
+        }
+    }
+}
+
+impl Vector for Vec3 {
+    const DIM: usize = 3;
+
+    fn zero() -> Vec3 {
+        Vec3::new(0.0, 0.0, 0.0)
+    }
+
+    fn get(&self, i: usize) -> f64 {
+        match i {
+            0 => self.x,
+            1 => self.y,
+            2 => self.z,
+            _ => panic!("Vec3 index out of range: {}", i)
+        }
+    }
+
+    fn set(&mut self, i: usize, v: f64) {
+        match i {
+            0 => self.x = v,
+            1 => self.y = v,
+            2 => self.z = v,
+            _ => panic!("Vec3 index out of range: {}", i)
+        }
+    }
+}
+
+impl Vector for Vec4 {
+    const DIM: usize = 4;
+
+    fn zero() -> Vec4 {
+        Vec4::new(0.0, 0.0, 0.0, 0.0)
+    }
+
+    fn get(&self, i: usize) -> f64 {
+        match i {
+            0 => self.x,
+            1 => self.y,
+            2 => self.z,
+            3 => self.w,
+            _ => panic!("Vec4 index out of range: {}", i)
+        }
+    }
+
+    fn set(&mut self, i: usize, v: f64) {
+        match i {
+            0 => self.x = v,
+            1 => self.y = v,
+            2 => self.z = v,
+            3 => self.w = v,
+            _ => panic!("Vec4 index out of range: {}", i)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Vector;
+    use crate::vector::vector2::Vec2;
+    use crate::vector::vector3::Vec3;
+    use crate::vector::vector4::Vec4;
+
+    fn lerp<V: Vector>(a: V, b: V, t: f64) -> V {
+        a + (b - a) * t
+    }
+
+    fn sum_components<V: Vector>(v: &V) -> f64 {
+        (0..V::DIM).map(|i| v.get(i)).sum()
+    }
+
+    #[test]
+    fn test_generic_lerp() {
+        let r = lerp(Vec2::new(0.0, 2.0), Vec2::new(2.0, 4.0), 0.5);
+        assert_eq!(r, Vec2::new(1.0, 3.0));
+
+        let r = lerp(Vec3::new(0.0, 2.0, -1.0), Vec3::new(2.0, 4.0, 1.0), 0.25);
+        assert_eq!(r, Vec3::new(0.5, 2.5, -0.5));
+    }
+
+    #[test]
+    fn test_dim() {
+        assert_eq!(Vec2::DIM, 2);
+        assert_eq!(Vec3::DIM, 3);
+        assert_eq!(Vec4::DIM, 4);
+    }
+
+    #[test]
+    fn test_get_set() {
+        let mut v = Vec3::zero();
+        v.set(1, 2.0);
+        v.set(2, 3.0);
+        assert_eq!(v, Vec3::new(0.0, 2.0, 3.0));
+        assert_eq!(v.get(2), 3.0);
+        assert_eq!(sum_components(&Vec4::new(1.0, 2.0, 3.0, 4.0)), 10.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_get_out_of_range() {
+        Vec2::zero().get(2);
+    }
+
+    #[test]
+    fn test_dot_magnitude() {
+        assert_eq!(Vector::dot(&Vec2::new(1.0, 2.0), &Vec2::new(3.0, 4.0)), 11.0);
+        assert_eq!(Vector::magnitude(&Vec2::new(3.0, 4.0)), 5.0);
+        assert_eq!(Vector::magnitude(&Vec3::new(2.0, 3.0, 6.0)), 7.0);
+        assert_eq!(Vector::magnitude(&Vec4::new(1.0, 1.0, 1.0, 1.0)), 2.0);
+    }
+}
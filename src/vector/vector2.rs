@@ -5,7 +5,8 @@ use std::ops::{
     Div, DivAssign,
     Neg
 };
-use std::cmp::{PartialEq};
+use std::cmp::{PartialEq, Ordering};
+use std::convert::TryInto;
 
 #[derive(Debug, Clone, Copy)]
 pub struct Vec2 {
@@ -19,9 +20,32 @@ impl std::fmt::Display for Vec2 {
     }
 }
 
+// Scientific notation, `{:e}` or `{:.3e}` apply to every component
+impl std::fmt::LowerExp for Vec2 {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Vec2(x: ")?;
+        std::fmt::LowerExp::fmt(&self.x, f)?;
+        write!(f, ", y: ")?;
+        std::fmt::LowerExp::fmt(&self.y, f)?;
+        write!(f, ")")
+    }
+}
+
 impl Vec2 {
-    pub fn new(x: f64, y: f64) -> Vec2 {
-        Vec2 { x: x, y: y }
+    pub const UNIT_X: Vec2 = Vec2::new(1.0, 0.0);
+    pub const UNIT_Y: Vec2 = Vec2::new(0.0, 1.0);
+
+    pub const fn new(x: f64, y: f64) -> Vec2 {
+        Vec2 { x, y }
+    }
+
+    pub fn into_tuple(self) -> (f64, f64) {
+        (self.x, self.y)
+    }
+
+    // Same layout as Display with `digits` decimals instead of 2
+    pub fn to_string_precision(&self, digits: usize) -> String {
+        format!("Vec2(x: {:.*}, y: {:.*})", digits, self.x, digits, self.y)
     }
 
     pub fn copy(&self) -> Vec2 {
@@ -36,9 +60,193 @@ impl Vec2 {
         (self.x * v.y) - (self.y * v.x)
     }
 
+    // Exterior (outer) product, the signed area of the parallelogram (self, other)
+    // Same value as cross, named for use in geometric predicates
+    pub fn wedge(&self, other: &Vec2) -> f64 {
+        self.cross(other)
+    }
+
+    // Scalar 2D cross product (z of the 3D cross), same value as `magnitude`
+    // Positive when other is counterclockwise from self, negative when clockwise
+    pub fn cross(&self, other: &Vec2) -> f64 {
+        self.x * other.y - self.y * other.x
+    }
+
     pub fn perpendicular(&self) -> Vec2 {
         Vec2::new(self.y, -self.x)
     }
+
+    pub fn abs(&self) -> Vec2 {
+        Vec2::new(self.x.abs(), self.y.abs())
+    }
+
+    pub fn floor(&self) -> Vec2 {
+        Vec2::new(self.x.floor(), self.y.floor())
+    }
+
+    pub fn ceil(&self) -> Vec2 {
+        Vec2::new(self.x.ceil(), self.y.ceil())
+    }
+
+    // Half-way cases are rounded away from 0, like f64::round
+    pub fn round(&self) -> Vec2 {
+        Vec2::new(self.x.round(), self.y.round())
+    }
+
+    // Little-endian wire format: x, y as f64
+    pub fn to_le_bytes(&self) -> [u8; 16] {
+        let mut r = [0; 16];
+        r[0..8].copy_from_slice(&self.x.to_le_bytes());
+        r[8..16].copy_from_slice(&self.y.to_le_bytes());
+        r
+    }
+
+    pub fn from_le_bytes(bytes: &[u8; 16]) -> Vec2 {
+        let f = |i: usize| f64::from_le_bytes(bytes[i..i + 8].try_into().unwrap());
+        Vec2::new(f(0), f(8))
+    }
+
+    // Angle in radians from +X toward +Y
+    pub fn from_polar(radius: f64, angle: f64) -> Vec2 {
+        Vec2::new(radius * angle.cos(), radius * angle.sin())
+    }
+
+    // Returns (radius, angle), the angle is in (-PI, PI]
+    pub fn to_polar(&self) -> (f64, f64) {
+        (self.norm(), self.y.atan2(self.x))
+    }
+
+    // Point `index` of the Halton low-discrepancy sequence in [0, 1)^2
+    // Bases must be coprime (usually 2 and 3), index 0 gives (0, 0)
+    // Panics if a base is lower than 2
+    pub fn halton(index: u64, base_x: u32, base_y: u32) -> Vec2 {
+        assert!(base_x >= 2 && base_y >= 2, "Halton bases must be at least 2: {}, {}", base_x, base_y);
+        Vec2::new(radical_inverse(index, base_x), radical_inverse(index, base_y))
+    }
+
+    pub fn norm(&self) -> f64 {
+        self.scalar(self).sqrt()
+    }
+
+    // True when no component is infinite or NaN
+    pub fn is_finite(&self) -> bool {
+        self.x.is_finite() && self.y.is_finite()
+    }
+
+    // True when any component is NaN
+    pub fn is_nan(&self) -> bool {
+        self.x.is_nan() || self.y.is_nan()
+    }
+
+    // Unit vector in the same direction, None for degenerate (near zero) vectors
+    pub fn try_normalize(&self) -> Option<Vec2> {
+        let norm = self.norm();
+        if norm < f64::EPSILON {
+            return None;
+        }
+        Some(Vec2::new(self.x / norm, self.y / norm))
+    }
+
+    // Average of the two points
+    pub fn midpoint(&self, other: &Vec2) -> Vec2 {
+        Vec2::new((self.x + other.x) * 0.5, (self.y + other.y) * 0.5)
+    }
+
+    // Steps toward target by at most max_delta, landing exactly on it when close enough
+    pub fn move_towards(&self, target: &Vec2, max_delta: f64) -> Vec2 {
+        let delta = *target - *self;
+        let distance = delta.norm();
+        if distance <= max_delta || distance == 0.0 {
+            return *target;
+        }
+        *self + delta * (max_delta / distance)
+    }
+
+    // Scales the vector down to max_len when it is longer, zero stays zero
+    pub fn clamp_magnitude(&self, max_len: f64) -> Vec2 {
+        let norm = self.norm();
+        if norm > max_len && norm > 0.0 {
+            *self * (max_len / norm)
+        } else {
+            *self
+        }
+    }
+
+    // Radial dead-zone (not per-axis) for analog sticks
+    // Below `inner` the input is zeroed, above `outer` it saturates to a unit vector
+    // In between the length is remapped from [inner, outer] to [0, 1]
+    // The direction of the input is always preserved
+    // Panics if outer <= inner
+    pub fn apply_deadzone(&self, inner: f64, outer: f64) -> Vec2 {
+        assert!(outer > inner, "Dead-zone outer radius must be greater than inner: {} <= {}", outer, inner);
+        let norm = self.norm();
+        if norm <= inner || norm == 0.0 {
+            return Vec2::new(0.0, 0.0);
+        }
+        let scaled = ((norm - inner) / (outer - inner)).min(1.0);
+        *self * (scaled / norm)
+    }
+
+    // Applies norm^exponent to the length while keeping the direction
+    // Expects an input already in the [0, 1] range (see apply_deadzone)
+    pub fn apply_response_curve(&self, exponent: f64) -> Vec2 {
+        let norm = self.norm();
+        if norm == 0.0 {
+            return self.copy();
+        }
+        *self * (norm.powf(exponent) / norm)
+    }
+}
+
+// One jittered sample per cell of a nx * ny grid over [0, 1)^2
+// Samples are ordered row by row (x first)
+#[cfg(feature = "rand")]
+pub fn stratified_samples_2d(nx: usize, ny: usize, rng: &mut impl rand::Rng) -> Vec<Vec2> {
+    let mut samples = Vec::with_capacity(nx * ny);
+    for j in 0..ny {
+        for i in 0..nx {
+            samples.push(Vec2::new(
+                (i as f64 + rng.gen::<f64>()) / nx as f64,
+                (j as f64 + rng.gen::<f64>()) / ny as f64
+            ));
+        }
+    }
+    samples
+}
+
+// Mirrors the digits of index written in `base` around the decimal point
+fn radical_inverse(mut index: u64, base: u32) -> f64 {
+    let base = base as u64;
+    let inv_base = 1.0 / base as f64;
+    let mut factor = inv_base;
+    let mut r = 0.0;
+
+    while index > 0 {
+        r += (index % base) as f64 * factor;
+        index /= base;
+        factor *= inv_base;
+    }
+    r
+}
+
+// Cross product of a scalar (a rotation around the z axis) with a vector
+// s x v = (-s * v.y, s * v.x), e.g. omega x r in 2D rigid body dynamics
+// Counterpart of the scalar perp-dot (Vec2::magnitude)
+pub fn cross_scalar(s: f64, v: Vec2) -> Vec2 {
+    Vec2::new(-s * v.y, s * v.x)
+}
+
+// Turn direction of the path a -> b -> c from the sign of the wedge product
+// Greater: counterclockwise (left turn), Less: clockwise, Equal: collinear
+pub fn orientation(a: &Vec2, b: &Vec2, c: &Vec2) -> Ordering {
+    let w = (*b - *a).wedge(&(*c - *a));
+    if w > 0.0 {
+        Ordering::Greater
+    } else if w < 0.0 {
+        Ordering::Less
+    } else {
+        Ordering::Equal
+    }
 }
 
 impl Add<Vec2> for Vec2 {
@@ -49,6 +257,14 @@ impl Add<Vec2> for Vec2 {
     }
 }
 
+impl Add<&Vec2> for &Vec2 {
+    type Output = Vec2;
+
+    fn add(self, rhs: &Vec2) -> Vec2 {
+        *self + *rhs
+    }
+}
+
 impl AddAssign<Vec2> for Vec2 {
     fn add_assign(&mut self, rhs: Vec2) {
         self.x += rhs.x;
@@ -64,6 +280,14 @@ impl Sub<Vec2> for Vec2 {
     }
 }
 
+impl Sub<&Vec2> for &Vec2 {
+    type Output = Vec2;
+
+    fn sub(self, rhs: &Vec2) -> Vec2 {
+        *self - *rhs
+    }
+}
+
 impl SubAssign<Vec2> for Vec2 {
     fn sub_assign(&mut self, rhs: Vec2) {
         self.x -= rhs.x;
@@ -79,6 +303,22 @@ impl Mul<f64> for Vec2 {
     }
 }
 
+impl Mul<f64> for &Vec2 {
+    type Output = Vec2;
+
+    fn mul(self, rhs: f64) -> Vec2 {
+        *self * rhs
+    }
+}
+
+impl Mul<Vec2> for f64 {
+    type Output = Vec2;
+
+    fn mul(self, rhs: Vec2) -> Vec2 {
+        rhs * self
+    }
+}
+
 impl MulAssign<f64> for Vec2 {
     fn mul_assign(&mut self, rhs: f64) {
         self.x *= rhs;
@@ -154,9 +394,41 @@ impl PartialEq for Vec2 {
     }
 }
 
+impl From<(f64, f64)> for Vec2 {
+    fn from(t: (f64, f64)) -> Vec2 {
+        Vec2::new(t.0, t.1)
+    }
+}
+
+// Iterates over x, y
+impl IntoIterator for Vec2 {
+    type Item = f64;
+    type IntoIter = std::array::IntoIter<f64, 2>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIterator::into_iter([self.x, self.y])
+    }
+}
+
+// Takes the first two values, missing ones are set to 0
+impl std::iter::FromIterator<f64> for Vec2 {
+    fn from_iter<I: IntoIterator<Item = f64>>(iter: I) -> Vec2 {
+        let mut it = iter.into_iter();
+        let mut next = || it.next().unwrap_or(0.0);
+        Vec2::new(next(), next())
+    }
+}
+
+// Componentwise sum starting from zero
+impl std::iter::Sum<Vec2> for Vec2 {
+    fn sum<I: Iterator<Item = Vec2>>(iter: I) -> Vec2 {
+        iter.fold(Vec2::new(0.0, 0.0), |acc, v| acc + v)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::Vec2;
+    use super::{Vec2, cross_scalar, orientation};
 
     #[test]
     fn basic_new() {
@@ -289,4 +561,303 @@ mod tests {
         let v = Vec2::new(1.252, 2.2);
         assert_eq!("Vec2(x: 1.25, y: 2.20)", format!("{}", v));
     }
+
+    #[test]
+    fn test_norm() {
+        let v = Vec2::new(3.0, 4.0);
+        assert_eq!(v.norm(), 5.0);
+    }
+
+    #[test]
+    fn test_deadzone_inner() {
+        let v = Vec2::new(0.1, 0.1);
+        assert_eq!(v.apply_deadzone(0.2, 0.9), Vec2::new(0.0, 0.0));
+    }
+
+    #[test]
+    fn test_deadzone_outer() {
+        let v = Vec2::new(0.0, 0.9);
+        let r = v.apply_deadzone(0.2, 0.9);
+        assert_approx_eq::assert_approx_eq!(r.x, 0.0, 0.000001);
+        assert_approx_eq::assert_approx_eq!(r.y, 1.0, 0.000001);
+
+        let r = Vec2::new(-3.0, 4.0).apply_deadzone(0.2, 0.9);
+        assert_approx_eq::assert_approx_eq!(r.norm(), 1.0, 0.000001);
+        assert_approx_eq::assert_approx_eq!(r.x, -0.6, 0.000001);
+        assert_approx_eq::assert_approx_eq!(r.y, 0.8, 0.000001);
+    }
+
+    #[test]
+    fn test_deadzone_keeps_direction() {
+        let v = Vec2::new(0.33, 0.44);
+        let r = v.apply_deadzone(0.3, 0.8);
+        assert_approx_eq::assert_approx_eq!(r.norm(), 0.5, 0.000001);
+        assert_approx_eq::assert_approx_eq!(r.x / r.y, 0.75, 0.000001);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_deadzone_invalid_range() {
+        Vec2::new(0.5, 0.5).apply_deadzone(0.9, 0.2);
+    }
+
+    #[test]
+    fn test_response_curve() {
+        let v = Vec2::new(0.0, 0.5);
+        let r = v.apply_response_curve(2.0);
+        assert_eq!(r, Vec2::new(0.0, 0.25));
+        assert_eq!(Vec2::new(0.0, 0.0).apply_response_curve(2.0), Vec2::new(0.0, 0.0));
+    }
+
+    #[test]
+    #[allow(clippy::op_ref)]
+    fn test_ref_ops() {
+        let v = Vec2::new(1.0, 2.0);
+        let v2 = Vec2::new(3.0, 4.0);
+        assert_eq!(&v + &v2, v + v2);
+        assert_eq!(&v - &v2, v - v2);
+        assert_eq!(&v * 3.0, v * 3.0);
+    }
+
+    #[test]
+    #[allow(clippy::op_ref)]
+    fn test_ref_ops_accumulate() {
+        let points = [Vec2::new(1.0, 2.0), Vec2::new(3.0, 4.0), Vec2::new(-1.0, 0.5)];
+        let mut sum = Vec2::new(0.0, 0.0);
+        for p in points.iter() {
+            sum = &sum + &(p * 2.0);
+            sum = &sum - p;
+        }
+        assert_eq!(sum, Vec2::new(3.0, 6.5));
+    }
+
+    #[test]
+    fn test_scalar_left_mul() {
+        let v = Vec2::new(1.0, -2.0);
+        assert_eq!(2.0 * v, v * 2.0);
+        assert_eq!(2.0 * v, Vec2::new(2.0, -4.0));
+    }
+
+    #[test]
+    fn test_le_bytes() {
+        let v = Vec2::new(1.0 / 3.0, -0.0);
+        let bytes = v.to_le_bytes();
+        assert_eq!(bytes.len(), 16);
+        let r = Vec2::from_le_bytes(&bytes);
+        assert_eq!(r.x.to_bits(), v.x.to_bits());
+        assert_eq!(r.y.to_bits(), v.y.to_bits());
+    }
+
+    #[test]
+    fn test_iter() {
+        let v: Vec2 = (3..10).map(|i| i as f64).collect();
+        assert_eq!(v, Vec2::new(3.0, 4.0));
+        assert_eq!(v.into_iter().sum::<f64>(), 7.0);
+    }
+
+    #[test]
+    fn test_cross_scalar() {
+        assert_eq!(cross_scalar(1.0, Vec2::UNIT_X), Vec2::UNIT_Y);
+        assert_eq!(cross_scalar(2.0, Vec2::UNIT_Y), Vec2::new(-2.0, 0.0));
+
+        let r = Vec2::new(3.0, -1.5);
+        assert_eq!(cross_scalar(0.7, r).scalar(&r), 0.0);
+    }
+
+    #[test]
+    fn test_abs_floor_ceil_round() {
+        let v = Vec2::new(-1.5, 2.4);
+        assert_eq!(v.abs(), Vec2::new(1.5, 2.4));
+        assert_eq!(v.floor(), Vec2::new(-2.0, 2.0));
+        assert_eq!(v.ceil(), Vec2::new(-1.0, 3.0));
+        assert_eq!(v.round(), Vec2::new(-2.0, 2.0));
+    }
+
+    #[test]
+    fn test_halton() {
+        let expected = [
+            (0.0, 0.0),
+            (1.0 / 2.0, 1.0 / 3.0),
+            (1.0 / 4.0, 2.0 / 3.0),
+            (3.0 / 4.0, 1.0 / 9.0),
+            (1.0 / 8.0, 4.0 / 9.0),
+            (5.0 / 8.0, 7.0 / 9.0)
+        ];
+        for (i, (x, y)) in expected.iter().enumerate() {
+            let p = Vec2::halton(i as u64, 2, 3);
+            assert_approx_eq::assert_approx_eq!(p.x, *x, 0.000001);
+            assert_approx_eq::assert_approx_eq!(p.y, *y, 0.000001);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_halton_base_zero() {
+        Vec2::halton(1, 0, 3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_halton_base_one() {
+        Vec2::halton(1, 2, 1);
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn test_stratified_samples_2d() {
+        use super::stratified_samples_2d;
+        use rand::SeedableRng;
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+        let (nx, ny) = (4, 3);
+        let samples = stratified_samples_2d(nx, ny, &mut rng);
+        assert_eq!(samples.len(), nx * ny);
+
+        for (k, p) in samples.iter().enumerate() {
+            let (i, j) = ((k % nx) as f64, (k / nx) as f64);
+            assert!(p.x >= i / nx as f64 && p.x < (i + 1.0) / nx as f64);
+            assert!(p.y >= j / ny as f64 && p.y < (j + 1.0) / ny as f64);
+        }
+    }
+
+    #[test]
+    fn test_polar_round_trip() {
+        use std::f64::consts::PI;
+
+        let points = [
+            Vec2::new(1.0, 2.0),
+            Vec2::new(-3.0, 0.5),
+            Vec2::new(-1.0, -1.0),
+            Vec2::new(0.2, -4.0)
+        ];
+        for p in points.iter() {
+            let (r, angle) = p.to_polar();
+            assert!(angle > -PI && angle <= PI);
+            let back = Vec2::from_polar(r, angle);
+            assert_approx_eq::assert_approx_eq!(back.x, p.x, 0.000001);
+            assert_approx_eq::assert_approx_eq!(back.y, p.y, 0.000001);
+        }
+        assert_eq!(Vec2::new(-1.0, 0.0).to_polar(), (1.0, PI));
+    }
+
+    #[test]
+    fn test_try_normalize() {
+        assert_eq!(Vec2::new(0.0, 0.0).try_normalize(), None);
+
+        let n = Vec2::new(3.0, 4.0).try_normalize().unwrap();
+        assert_approx_eq::assert_approx_eq!(n.norm(), 1.0, 0.000001);
+        assert_approx_eq::assert_approx_eq!(n.x, 0.6, 0.000001);
+        assert_approx_eq::assert_approx_eq!(n.y, 0.8, 0.000001);
+    }
+
+    #[test]
+    fn test_move_towards() {
+        let from = Vec2::new(1.0, 2.0);
+        let target = Vec2::new(4.0, 6.0);
+        assert_eq!(from.move_towards(&target, 10.0), target);
+
+        let r = from.move_towards(&target, 2.5);
+        assert_approx_eq::assert_approx_eq!(r.x, 2.5, 0.000001);
+        assert_approx_eq::assert_approx_eq!(r.y, 4.0, 0.000001);
+    }
+
+    #[test]
+    fn test_clamp_magnitude() {
+        let v = Vec2::new(3.0, 4.0);
+        assert_eq!(v.clamp_magnitude(6.0), v);
+
+        let r = v.clamp_magnitude(1.0);
+        assert_approx_eq::assert_approx_eq!(r.x, 0.6, 0.000001);
+        assert_approx_eq::assert_approx_eq!(r.y, 0.8, 0.000001);
+        assert_eq!(Vec2::new(0.0, 0.0).clamp_magnitude(1.0), Vec2::new(0.0, 0.0));
+    }
+
+    #[test]
+    fn test_sum() {
+        let vecs = [Vec2::new(1.0, 2.0), Vec2::new(-3.0, 0.5), Vec2::new(0.0, 1.5)];
+        assert_eq!(vecs.iter().copied().sum::<Vec2>(), Vec2::new(-2.0, 4.0));
+        assert_eq!(std::iter::empty::<Vec2>().sum::<Vec2>(), Vec2::new(0.0, 0.0));
+    }
+
+    #[test]
+    fn test_cross() {
+        let x = Vec2::new(1.0, 0.0);
+        let y = Vec2::new(0.0, 1.0);
+        assert_eq!(x.cross(&y), 1.0);
+        assert_eq!(y.cross(&x), -1.0);
+        assert_eq!(x.cross(&Vec2::new(-3.0, 0.0)), 0.0);
+
+        let a = Vec2::new(2.0, 1.0);
+        let b = Vec2::new(-1.0, 3.0);
+        assert_eq!(a.cross(&b), 7.0);
+        assert_eq!(a.cross(&b), a.magnitude(&b));
+    }
+
+    #[test]
+    fn test_is_finite_is_nan() {
+        assert!(Vec2::new(1.0, -2.0).is_finite());
+        assert!(!Vec2::new(f64::INFINITY, 0.0).is_finite());
+        assert!(!Vec2::new(0.0, f64::NAN).is_finite());
+        assert!(Vec2::new(0.0, f64::NAN).is_nan());
+        assert!(!Vec2::new(f64::NEG_INFINITY, 0.0).is_nan());
+    }
+
+    #[test]
+    fn test_wedge() {
+        let a = Vec2::new(2.0, 0.0);
+        let b = Vec2::new(1.0, 3.0);
+        assert_eq!(a.wedge(&b), 6.0);
+        assert_eq!(b.wedge(&a), -6.0);
+        assert_eq!(a.wedge(&a), 0.0);
+    }
+
+    #[test]
+    fn test_orientation() {
+        use std::cmp::Ordering;
+
+        let a = Vec2::new(0.0, 0.0);
+        let b = Vec2::new(1.0, 0.0);
+        assert_eq!(orientation(&a, &b, &Vec2::new(1.0, 1.0)), Ordering::Greater);
+        assert_eq!(orientation(&a, &b, &Vec2::new(1.0, -1.0)), Ordering::Less);
+        assert_eq!(orientation(&a, &b, &Vec2::new(3.0, 0.0)), Ordering::Equal);
+        assert_eq!(orientation(&a, &b, &Vec2::new(-2.0, 0.0)), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_const_new() {
+        const ORIGIN: Vec2 = Vec2::new(0.0, 0.0);
+        static DOWN: Vec2 = Vec2::new(0.0, -1.0);
+        assert_eq!(ORIGIN + DOWN, Vec2::new(0.0, -1.0));
+    }
+
+    #[test]
+    fn test_tuple() {
+        let v = Vec2::new(1.5, -2.0);
+        let (x, y) = v.into_tuple();
+        assert_eq!((x, y), (1.5, -2.0));
+        assert_eq!(Vec2::from(v.into_tuple()), v);
+        let w: Vec2 = (3.0, 4.0).into();
+        assert_eq!(w, Vec2::new(3.0, 4.0));
+    }
+
+    #[test]
+    fn test_to_string_precision() {
+        let v = Vec2::new(1.23456789, -0.5);
+        assert_eq!(v.to_string_precision(0), "Vec2(x: 1, y: -0)");
+        assert_eq!(v.to_string_precision(2), v.to_string());
+        assert_eq!(v.to_string_precision(6), "Vec2(x: 1.234568, y: -0.500000)");
+    }
+
+    #[test]
+    fn test_lower_exp() {
+        let v = Vec2::new(1e-10, 2500.0);
+        assert_eq!(format!("{:e}", v), "Vec2(x: 1e-10, y: 2.5e3)");
+        assert_eq!(v.to_string(), "Vec2(x: 0.00, y: 2500.00)");
+    }
+
+    #[test]
+    fn test_midpoint() {
+        assert_eq!(Vec2::new(0.0, 0.0).midpoint(&Vec2::new(2.0, 4.0)), Vec2::new(1.0, 2.0));
+        assert_eq!(Vec2::new(-3.0, 1.0).midpoint(&Vec2::new(3.0, 1.0)), Vec2::new(0.0, 1.0));
+    }
 }
@@ -6,7 +6,10 @@ use std::ops::{
     Rem, RemAssign,
     Neg
 };
-use std::cmp::{PartialEq};
+use std::cmp::{PartialEq, Ordering};
+use std::convert::TryInto;
+use crate::math::{wrap_angle, smoothstep, smootherstep, Ease};
+use super::vector2::Vec2;
 
 #[derive(Clone, Copy, Debug)]
 pub struct Vec3 {
@@ -27,6 +30,19 @@ impl std::fmt::Display for Vec3 {
     }
 }
 
+// Scientific notation, `{:e}` or `{:.3e}` apply to every component
+impl std::fmt::LowerExp for Vec3 {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Vec3(x: ")?;
+        std::fmt::LowerExp::fmt(&self.x, f)?;
+        write!(f, ", y: ")?;
+        std::fmt::LowerExp::fmt(&self.y, f)?;
+        write!(f, ", z: ")?;
+        std::fmt::LowerExp::fmt(&self.z, f)?;
+        write!(f, ")")
+    }
+}
+
 impl PartialEq<Vec3> for Vec3 {
     fn eq(&self, other: &Vec3) -> bool {
         self.x == other.x &&
@@ -36,8 +52,22 @@ impl PartialEq<Vec3> for Vec3 {
 }
 
 impl Vec3 {
-    pub fn new(x: f64, y: f64, z: f64) -> Vec3 {
-        Vec3 { x: x, y: y, z: z }
+    pub const fn new(x: f64, y: f64, z: f64) -> Vec3 {
+        Vec3 { x, y, z }
+    }
+
+    pub fn into_tuple(self) -> (f64, f64, f64) {
+        (self.x, self.y, self.z)
+    }
+
+    // Same layout as Display with `digits` decimals instead of 2
+    pub fn to_string_precision(&self, digits: usize) -> String {
+        format!(
+            "Vec3(x: {:.*}, y: {:.*}, z: {:.*})",
+            digits, self.x,
+            digits, self.y,
+            digits, self.z
+        )
     }
 
     pub fn copy(&self) -> Vec3 {
@@ -58,7 +88,7 @@ impl Vec3 {
 
     pub fn get_normalize(&self) -> Vec3 {
         let magnitude = self.magnitude();
-        return if magnitude > 0.0 {
+        if magnitude > 0.0 {
             *self * (1.0 / magnitude)
         } else {
             self.copy()
@@ -68,10 +98,61 @@ impl Vec3 {
     pub fn normalize(&mut self) {
         let magnitude = self.magnitude();
         if magnitude > 0.0 {
-            *self = *self * (1.0 / magnitude)
+            *self *= 1.0 / magnitude
+        }
+    }
+
+    // True when no component is infinite or NaN
+    pub fn is_finite(&self) -> bool {
+        self.x.is_finite() && self.y.is_finite() && self.z.is_finite()
+    }
+
+    // True when any component is NaN
+    pub fn is_nan(&self) -> bool {
+        self.x.is_nan() || self.y.is_nan() || self.z.is_nan()
+    }
+
+    // Like get_normalize but reports degenerate (near zero) vectors
+    pub fn try_normalize(&self) -> Option<Vec3> {
+        let magnitude = self.magnitude();
+        if magnitude < f64::EPSILON {
+            return None;
+        }
+        Some(*self * (1.0 / magnitude))
+    }
+
+    // Steps toward target by at most max_delta, landing exactly on it when close enough
+    pub fn move_towards(&self, target: &Vec3, max_delta: f64) -> Vec3 {
+        let delta = *target - *self;
+        let distance = delta.magnitude();
+        if distance <= max_delta || distance == 0.0 {
+            return *target;
+        }
+        *self + delta * (max_delta / distance)
+    }
+
+    // Moves `amount` along direction whatever its length
+    // A zero direction leaves self unchanged
+    pub fn step(&self, direction: &Vec3, amount: f64) -> Vec3 {
+        match direction.try_normalize() {
+            Some(dir) => *self + dir * amount,
+            None => *self
+        }
+    }
+
+    // Scales the vector down to max_len when it is longer, zero stays zero
+    pub fn clamp_magnitude(&self, max_len: f64) -> Vec3 {
+        let magnitude = self.magnitude();
+        if magnitude > max_len && magnitude > 0.0 {
+            *self * (max_len / magnitude)
+        } else {
+            *self
         }
     }
 
+    // Right-handed cross product: x cross y = z, y cross x = -z
+    // Perpendicular to both inputs, its length is the area of their parallelogram
+    // (% is the componentwise remainder, not the cross product)
     pub fn cross(&self, v: &Vec3) -> Vec3 {
         Vec3::new(
             self.y * v.z - self.z * v.y,
@@ -80,9 +161,340 @@ impl Vec3 {
         )
     }
 
+    // a . (b x c), signed volume of the parallelepiped
+    pub fn scalar_triple(&self, b: &Vec3, c: &Vec3) -> f64 {
+        self.dot(&b.cross(c))
+    }
+
+    // a x (b x c)
+    pub fn vector_triple(&self, b: &Vec3, c: &Vec3) -> Vec3 {
+        self.cross(&b.cross(c))
+    }
+
     pub fn perpendicular(&self, v: &Vec3) -> Vec3 {
         *self * self.dot(v)
     }
+
+    // Reflects self about a unit normal: r = d - 2 (d . n) n
+    pub fn reflect(&self, normal: &Vec3) -> Vec3 {
+        *self - *normal * (2.0 * self.dot(normal))
+    }
+
+    // Snell's law for a unit incident direction and a unit normal facing it
+    // eta is n_incident / n_transmitted, None on total internal reflection
+    pub fn refract(&self, normal: &Vec3, eta: f64) -> Option<Vec3> {
+        let cos_i = -self.dot(normal);
+        let k = 1.0 - eta * eta * (1.0 - cos_i * cos_i);
+        if k < 0.0 {
+            return None;
+        }
+        Some(*self * eta + *normal * (eta * cos_i - k.sqrt()))
+    }
+
+    // reflect followed by get_normalize, `normal` must be unit length
+    pub fn reflect_normalized(&self, normal: &Vec3) -> Vec3 {
+        self.reflect(normal).get_normalize()
+    }
+
+    // Re-projects a horizontal movement (world up is +Y) onto the ground plane
+    // The heading seen from above is kept and the length is the one of self,
+    // so the speed stays constant on slopes. Walls fall back to a plain projection
+    pub fn align_to_ground(&self, ground_normal: Vec3) -> Vec3 {
+        let n = ground_normal.get_normalize();
+        let length = self.magnitude();
+        let moved = if n.y.abs() < f64::EPSILON {
+            *self - n * self.dot(&n)
+        } else {
+            Vec3::new(self.x, -(self.x * n.x + self.z * n.z) / n.y, self.z)
+        };
+        match moved.try_normalize() {
+            Some(dir) => dir * length,
+            None => moved
+        }
+    }
+
+    // Spherical interpolation between two directions, the result is unit length
+    // Opposite directions rotate around an arbitrary perpendicular axis
+    pub fn slerp(&self, other: &Vec3, t: f64) -> Vec3 {
+        let a = self.get_normalize();
+        let b = other.get_normalize();
+        let theta = a.dot(&b).clamp(-1.0, 1.0).acos();
+
+        if theta < 1e-6 {
+            return (a + (b - a) * t).get_normalize();
+        }
+        if std::f64::consts::PI - theta < 1e-6 {
+            let p = a.any_perpendicular();
+            return a * (theta * t).cos() + p * (theta * t).sin();
+        }
+        (a * ((1.0 - t) * theta).sin() + b * (t * theta).sin()) * (1.0 / theta.sin())
+    }
+
+    // Y up spherical coordinates, angles in radians
+    // theta: polar angle from +Y, phi: azimuth from +X toward +Z
+    pub fn from_spherical(radius: f64, theta: f64, phi: f64) -> Vec3 {
+        let (sin_theta, cos_theta) = theta.sin_cos();
+        let (sin_phi, cos_phi) = phi.sin_cos();
+        Vec3::new(
+            radius * sin_theta * cos_phi,
+            radius * cos_theta,
+            radius * sin_theta * sin_phi
+        )
+    }
+
+    // Returns (radius, theta, phi), see from_spherical
+    // The angles are 0 for a zero vector
+    pub fn to_spherical(&self) -> (f64, f64, f64) {
+        let radius = self.magnitude();
+        if radius == 0.0 {
+            return (0.0, 0.0, 0.0);
+        }
+        let theta = (self.y / radius).clamp(-1.0, 1.0).acos();
+        (radius, theta, self.z.atan2(self.x))
+    }
+
+    // Maps a uniform sample u in [0, 1)^2 to a unit direction in the hemisphere
+    // around `normal` with a cosine weighted density (Malley's method)
+    pub fn cosine_weighted_hemisphere(normal: Vec3, u: Vec2) -> Vec3 {
+        let n = normal.get_normalize();
+        let t = n.any_perpendicular();
+        let b = n.cross(&t);
+
+        // Uniform point on the unit disk lifted onto the hemisphere
+        let r = u.x.sqrt();
+        let phi = 2.0 * std::f64::consts::PI * u.y;
+        let z = (1.0 - u.x).max(0.0).sqrt();
+        t * (r * phi.cos()) + b * (r * phi.sin()) + n * z
+    }
+
+    // Linear interpolation with t remapped by an easing curve
+    pub fn ease(&self, other: &Vec3, t: f64, easing: Ease) -> Vec3 {
+        *self + (*other - *self) * easing.apply(t)
+    }
+
+    // Interpolates the direction with slerp and the length linearly
+    // Unlike a lerp, blending opposite vectors does not shrink through zero
+    pub fn blend(&self, other: &Vec3, t: f64) -> Vec3 {
+        let from = self.magnitude();
+        let to = other.magnitude();
+        if from == 0.0 || to == 0.0 {
+            return *self + (*other - *self) * t;
+        }
+        self.slerp(other, t) * (from + (to - from) * t)
+    }
+
+    // Tangent and bitangent completing a right-handed basis (t, b, self)
+    // self must be unit length, branchless method from Duff et al. 2017
+    pub fn orthonormal_basis(&self) -> (Vec3, Vec3) {
+        let sign = 1.0_f64.copysign(self.z);
+        let a = -1.0 / (sign + self.z);
+        let b = self.x * self.y * a;
+        (
+            Vec3::new(1.0 + sign * self.x * self.x * a, sign * b, -sign * self.x),
+            Vec3::new(b, sign + self.y * self.y * a, -self.y)
+        )
+    }
+
+    // Unit vector perpendicular to self, self must not be zero
+    fn any_perpendicular(&self) -> Vec3 {
+        let axis = if self.x.abs() < 0.9 {
+            Vec3::new(1.0, 0.0, 0.0)
+        } else {
+            Vec3::new(0.0, 1.0, 0.0)
+        };
+        self.cross(&axis).get_normalize()
+    }
+
+    // Little-endian wire format: x, y, z as f64
+    pub fn to_le_bytes(&self) -> [u8; 24] {
+        let mut r = [0; 24];
+        r[0..8].copy_from_slice(&self.x.to_le_bytes());
+        r[8..16].copy_from_slice(&self.y.to_le_bytes());
+        r[16..24].copy_from_slice(&self.z.to_le_bytes());
+        r
+    }
+
+    pub fn from_le_bytes(bytes: &[u8; 24]) -> Vec3 {
+        let f = |i: usize| f64::from_le_bytes(bytes[i..i + 8].try_into().unwrap());
+        Vec3::new(f(0), f(8), f(16))
+    }
+
+    // Raw bit patterns of the components, usable as a Hash/Eq key
+    // Every NaN is mapped to the same pattern so they all compare equal
+    pub fn to_bits(&self) -> (u64, u64, u64) {
+        let bits = |v: f64| if v.is_nan() { f64::NAN.to_bits() } else { v.to_bits() };
+        (bits(self.x), bits(self.y), bits(self.z))
+    }
+
+    // Equality of the bit patterns (see to_bits): unlike ==, -0.0 and 0.0
+    // differ and NaN equals NaN, handy for serialization round-trip tests
+    pub fn bit_eq(&self, other: &Vec3) -> bool {
+        self.to_bits() == other.to_bits()
+    }
+
+    // Wraps every component into [-PI, PI), for Euler angles that drifted
+    pub fn wrap_angles(&self) -> Vec3 {
+        Vec3::new(
+            wrap_angle(self.x),
+            wrap_angle(self.y),
+            wrap_angle(self.z)
+        )
+    }
+
+    pub fn abs(&self) -> Vec3 {
+        Vec3::new(self.x.abs(), self.y.abs(), self.z.abs())
+    }
+
+    pub fn floor(&self) -> Vec3 {
+        Vec3::new(self.x.floor(), self.y.floor(), self.z.floor())
+    }
+
+    pub fn ceil(&self) -> Vec3 {
+        Vec3::new(self.x.ceil(), self.y.ceil(), self.z.ceil())
+    }
+
+    // Half-way cases are rounded away from 0, like f64::round
+    pub fn round(&self) -> Vec3 {
+        Vec3::new(self.x.round(), self.y.round(), self.z.round())
+    }
+
+    // Componentwise smoothstep of x between the edges
+    pub fn smoothstep(edge0: &Vec3, edge1: &Vec3, x: &Vec3) -> Vec3 {
+        Vec3::new(
+            smoothstep(edge0.x, edge1.x, x.x),
+            smoothstep(edge0.y, edge1.y, x.y),
+            smoothstep(edge0.z, edge1.z, x.z)
+        )
+    }
+
+    // Componentwise smootherstep of x between the edges
+    pub fn smootherstep(edge0: &Vec3, edge1: &Vec3, x: &Vec3) -> Vec3 {
+        Vec3::new(
+            smootherstep(edge0.x, edge1.x, x.x),
+            smootherstep(edge0.y, edge1.y, x.y),
+            smootherstep(edge0.z, edge1.z, x.z)
+        )
+    }
+
+    // Scalar projection onto axis (any non-zero length), a sort key for sweeps
+    // along arbitrary directions, compare keys with math::total_cmp_f64
+    // A zero axis gives NaN for every point, which sorts them meaninglessly
+    pub fn key_along(&self, axis: Vec3) -> f64 {
+        self.dot(&axis) / axis.magnitude()
+    }
+
+    // self * scale + add with a single rounding per component (f64::mul_add)
+    pub fn mul_add(&self, scale: f64, add: &Vec3) -> Vec3 {
+        Vec3::new(
+            self.x.mul_add(scale, add.x),
+            self.y.mul_add(scale, add.y),
+            self.z.mul_add(scale, add.z)
+        )
+    }
+
+    // Componentwise minimum
+    pub fn min(&self, other: &Vec3) -> Vec3 {
+        Vec3::new(self.x.min(other.x), self.y.min(other.y), self.z.min(other.z))
+    }
+
+    // Componentwise maximum
+    pub fn max(&self, other: &Vec3) -> Vec3 {
+        Vec3::new(self.x.max(other.x), self.y.max(other.y), self.z.max(other.z))
+    }
+
+    // Componentwise comparisons, combine them with all / any
+    pub fn cmplt(&self, other: &Vec3) -> (bool, bool, bool) {
+        (self.x < other.x, self.y < other.y, self.z < other.z)
+    }
+
+    pub fn cmpgt(&self, other: &Vec3) -> (bool, bool, bool) {
+        (self.x > other.x, self.y > other.y, self.z > other.z)
+    }
+
+    pub fn cmpeq(&self, other: &Vec3) -> (bool, bool, bool) {
+        (self.x == other.x, self.y == other.y, self.z == other.z)
+    }
+
+    // Deterministic total order: x, then y, then z with f64::total_cmp
+    // NaN sorts after every number, usable with sort_by and dedup
+    pub fn lexicographic_cmp(&self, other: &Vec3) -> Ordering {
+        self.x.total_cmp(&other.x)
+            .then(self.y.total_cmp(&other.y))
+            .then(self.z.total_cmp(&other.z))
+    }
+
+    // Uniform Catmull-Rom spline between p1 (t = 0) and p2 (t = 1)
+    // p0 and p3 only shape the tangents: (p2 - p0) / 2 and (p3 - p1) / 2
+    pub fn catmull_rom(p0: Vec3, p1: Vec3, p2: Vec3, p3: Vec3, t: f64) -> Vec3 {
+        let t2 = t * t;
+        let t3 = t2 * t;
+        (p1 * 2.0
+            + (p2 - p0) * t
+            + (p0 * 2.0 - p1 * 5.0 + p2 * 4.0 - p3) * t2
+            + (p1 * 3.0 - p0 - p2 * 3.0 + p3) * t3) * 0.5
+    }
+
+    // Cubic Bezier curve in Bernstein form, p0 at t = 0 and p3 at t = 1
+    pub fn cubic_bezier(p0: Vec3, p1: Vec3, p2: Vec3, p3: Vec3, t: f64) -> Vec3 {
+        let u = 1.0 - t;
+        p0 * (u * u * u) + p1 * (3.0 * u * u * t) + p2 * (3.0 * u * t * t) + p3 * (t * t * t)
+    }
+
+    // Derivative of cubic_bezier with respect to t (not normalized)
+    pub fn cubic_bezier_tangent(p0: Vec3, p1: Vec3, p2: Vec3, p3: Vec3, t: f64) -> Vec3 {
+        let u = 1.0 - t;
+        (p1 - p0) * (3.0 * u * u) + (p2 - p1) * (6.0 * u * t) + (p3 - p2) * (3.0 * t * t)
+    }
+
+    // Arithmetic mean of the points, an empty slice gives zero
+    pub fn centroid(points: &[Vec3]) -> Vec3 {
+        if points.is_empty() {
+            return Vec3::new(0.0, 0.0, 0.0);
+        }
+        points.iter().copied().sum::<Vec3>() / points.len() as f64
+    }
+
+    // Point of the triangle (a, b, c) at barycentric coordinates (1 - u - v, u, v)
+    pub fn barycentric(a: &Vec3, b: &Vec3, c: &Vec3, u: f64, v: f64) -> Vec3 {
+        *a * (1.0 - u - v) + *b * u + *c * v
+    }
+
+    // Barycentric weights (of a, b, c) of p projected on the plane of the triangle
+    // The weights are NaN for a degenerate triangle
+    pub fn compute_barycentric(p: &Vec3, a: &Vec3, b: &Vec3, c: &Vec3) -> (f64, f64, f64) {
+        let v0 = *b - *a;
+        let v1 = *c - *a;
+        let v2 = *p - *a;
+        let d00 = v0.dot(&v0);
+        let d01 = v0.dot(&v1);
+        let d11 = v1.dot(&v1);
+        let d20 = v2.dot(&v0);
+        let d21 = v2.dot(&v1);
+        let denom = d00 * d11 - d01 * d01;
+
+        let v = (d11 * d20 - d01 * d21) / denom;
+        let w = (d00 * d21 - d01 * d20) / denom;
+        (1.0 - v - w, v, w)
+    }
+
+    // Hadamard product, Vec3 * Vec3 is kept as the dot product
+    pub fn mul_componentwise(&self, other: &Vec3) -> Vec3 {
+        Vec3::new(
+            self.x * other.x,
+            self.y * other.y,
+            self.z * other.z
+        )
+    }
+
+    // A zero component in `other` gives a zero component instead of inf/NaN
+    pub fn div_componentwise(&self, other: &Vec3) -> Vec3 {
+        let div = |a: f64, b: f64| if b == 0.0 { 0.0 } else { a / b };
+        Vec3::new(
+            div(self.x, other.x),
+            div(self.y, other.y),
+            div(self.z, other.z)
+        )
+    }
 }
 
 impl Add<Vec3> for Vec3 {
@@ -97,6 +509,14 @@ impl Add<Vec3> for Vec3 {
     }
 }
 
+impl Add<&Vec3> for &Vec3 {
+    type Output = Vec3;
+
+    fn add(self, v: &Vec3) -> Vec3 {
+        *self + *v
+    }
+}
+
 impl AddAssign for Vec3 {
     fn add_assign(&mut self, v: Vec3) {
         self.x += v.x;
@@ -117,6 +537,14 @@ impl Sub<Vec3> for Vec3 {
     }
 }
 
+impl Sub<&Vec3> for &Vec3 {
+    type Output = Vec3;
+
+    fn sub(self, v: &Vec3) -> Vec3 {
+        *self - *v
+    }
+}
+
 impl SubAssign<Vec3> for Vec3 {
     fn sub_assign(&mut self, v: Vec3) {
         self.x -= v.x;
@@ -143,6 +571,22 @@ impl Mul<f64> for Vec3 {
     }
 }
 
+impl Mul<f64> for &Vec3 {
+    type Output = Vec3;
+
+    fn mul(self, s: f64) -> Vec3 {
+        *self * s
+    }
+}
+
+impl Mul<Vec3> for f64 {
+    type Output = Vec3;
+
+    fn mul(self, v: Vec3) -> Vec3 {
+        v * self
+    }
+}
+
 impl MulAssign<f64> for Vec3 {
     fn mul_assign(&mut self, s: f64) {
         self.x *= s;
@@ -229,9 +673,52 @@ impl Neg for Vec3 {
     }
 }
 
+// True if every component of a comparison mask is true
+pub fn all(mask: (bool, bool, bool)) -> bool {
+    mask.0 && mask.1 && mask.2
+}
+
+// True if at least one component of a comparison mask is true
+pub fn any(mask: (bool, bool, bool)) -> bool {
+    mask.0 || mask.1 || mask.2
+}
+
+impl From<(f64, f64, f64)> for Vec3 {
+    fn from(t: (f64, f64, f64)) -> Vec3 {
+        Vec3::new(t.0, t.1, t.2)
+    }
+}
+
+// Iterates over x, y, z
+impl IntoIterator for Vec3 {
+    type Item = f64;
+    type IntoIter = std::array::IntoIter<f64, 3>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIterator::into_iter([self.x, self.y, self.z])
+    }
+}
+
+// Takes the first three values, missing ones are set to 0
+impl std::iter::FromIterator<f64> for Vec3 {
+    fn from_iter<I: IntoIterator<Item = f64>>(iter: I) -> Vec3 {
+        let mut it = iter.into_iter();
+        let mut next = || it.next().unwrap_or(0.0);
+        Vec3::new(next(), next(), next())
+    }
+}
+
+// Componentwise sum starting from zero
+impl std::iter::Sum<Vec3> for Vec3 {
+    fn sum<I: Iterator<Item = Vec3>>(iter: I) -> Vec3 {
+        iter.fold(Vec3::new(0.0, 0.0, 0.0), |acc, v| acc + v)
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use super::Vec3;
+    use super::{Vec3, all, any};
+    use crate::vector::vector2::Vec2;
 
     #[test]
     fn create_basic_vec3() {
@@ -371,8 +858,9 @@ mod test {
         let r = v1.cross(&v2);
         assert_approx_eq::assert_approx_eq!(r.x, -1362.0508, 0.0001);
         assert_approx_eq::assert_approx_eq!(r.y, 11.5016, 0.0001);
-        assert_approx_eq::assert_approx_eq!(r.y, 11.5016, 0.0001);
         assert_approx_eq::assert_approx_eq!(r.z, 249.107738, 0.00001);
+        assert_approx_eq::assert_approx_eq!(r.dot(&v1), 0.0, 0.001);
+        assert_approx_eq::assert_approx_eq!(r.dot(&v2), 0.0, 0.001);
     }
 
     #[test]
@@ -400,4 +888,662 @@ mod test {
         assert_eq!(v1, v2.get_normalize());
     }
 
+    #[test]
+    #[allow(clippy::op_ref)]
+    fn test_ref_ops() {
+        let v1 = Vec3::new(1.0, 2.0, 3.0);
+        let v2 = Vec3::new(4.0, 5.0, 6.0);
+        assert_eq!(&v1 + &v2, v1 + v2);
+        assert_eq!(&v1 - &v2, v1 - v2);
+        assert_eq!(&v1 * 2.0, v1 * 2.0);
+    }
+
+    #[test]
+    #[allow(clippy::op_ref)]
+    fn test_ref_ops_accumulate() {
+        let points = [
+            Vec3::new(1.0, 2.0, 3.0),
+            Vec3::new(4.0, 5.0, 6.0),
+            Vec3::new(-1.0, 0.5, 2.0)
+        ];
+        let mut sum = Vec3::new(0.0, 0.0, 0.0);
+        for p in points.iter() {
+            sum = &sum + &(p * 2.0);
+            sum = &sum - p;
+        }
+        assert_eq!(sum, Vec3::new(4.0, 7.5, 11.0));
+    }
+
+    #[test]
+    fn test_scalar_left_mul() {
+        let v = Vec3::new(1.0, -2.0, 3.5);
+        assert_eq!(2.0 * v, v * 2.0);
+        assert_eq!(2.0 * v, Vec3::new(2.0, -4.0, 7.0));
+    }
+
+    #[test]
+    fn test_mul_componentwise() {
+        let v1 = Vec3::new(2.0, 3.0, 4.0);
+        let v2 = Vec3::new(1.0, 2.0, 0.5);
+        assert_eq!(v1.mul_componentwise(&v2), Vec3::new(2.0, 6.0, 2.0));
+        assert_eq!(v1 * v2, 10.0);
+    }
+
+    #[test]
+    fn test_div_componentwise() {
+        let v1 = Vec3::new(2.0, 6.0, 2.0);
+        let v2 = Vec3::new(1.0, 2.0, 0.5);
+        assert_eq!(v1.div_componentwise(&v2), Vec3::new(2.0, 3.0, 4.0));
+        assert_eq!(v1.div_componentwise(&Vec3::new(2.0, 0.0, 1.0)), Vec3::new(1.0, 0.0, 2.0));
+    }
+
+    #[test]
+    fn test_to_bits() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+
+        let hash = |v: &Vec3| {
+            let mut h = DefaultHasher::new();
+            v.to_bits().hash(&mut h);
+            h.finish()
+        };
+        let v1 = Vec3::new(1.0, 2.0, 3.0);
+        let v2 = Vec3::new(1.0, 2.0, 3.0);
+        assert_eq!(v1.to_bits(), v2.to_bits());
+        assert_eq!(hash(&v1), hash(&v2));
+        assert_ne!(v1.to_bits(), Vec3::new(1.0, 2.0, 4.0).to_bits());
+
+        let n1 = Vec3::new(f64::NAN, 0.0, 0.0);
+        let n2 = Vec3::new(-f64::NAN, 0.0, 0.0);
+        assert_ne!(n1, n2);
+        assert_eq!(n1.to_bits(), n2.to_bits());
+        assert_eq!(hash(&n1), hash(&n2));
+    }
+
+    #[test]
+    fn test_to_bits_hashmap() {
+        use std::collections::HashMap;
+
+        let mut grid = HashMap::new();
+        grid.insert(Vec3::new(1.0, 0.0, 2.0).to_bits(), "a");
+        grid.insert(Vec3::new(0.0, 1.0, 2.0).to_bits(), "b");
+        assert_eq!(grid.get(&Vec3::new(1.0, 0.0, 2.0).to_bits()), Some(&"a"));
+        assert_eq!(grid.get(&Vec3::new(2.0, 0.0, 2.0).to_bits()), None);
+    }
+
+    #[test]
+    fn test_reflect() {
+        let d = Vec3::new(1.0, -1.0, 0.0);
+        let n = Vec3::new(0.0, 1.0, 0.0);
+        assert_eq!(d.reflect(&n), Vec3::new(1.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn test_reflect_normalized() {
+        let d = Vec3::new(3.0, -2.0, 1.5);
+        let n = Vec3::new(1.0, 2.0, -0.5).get_normalize();
+        let r = d.reflect_normalized(&n);
+        assert_approx_eq::assert_approx_eq!(r.magnitude(), 1.0, 0.000001);
+        assert_eq!(r, d.reflect(&n).get_normalize());
+    }
+
+    #[test]
+    fn test_slerp() {
+        let a = Vec3::new(1.0, 0.0, 0.0);
+        let b = Vec3::new(0.0, 2.0, 0.0);
+        let r = a.slerp(&b, 0.5);
+        let h = 0.5_f64.sqrt();
+        assert_approx_eq::assert_approx_eq!(r.x, h, 0.000001);
+        assert_approx_eq::assert_approx_eq!(r.y, h, 0.000001);
+        assert_approx_eq::assert_approx_eq!(r.z, 0.0, 0.000001);
+        assert_eq!(a.slerp(&b, 0.0), a);
+    }
+
+    #[test]
+    fn test_blend_opposite_keeps_length() {
+        let a = Vec3::new(2.0, 0.0, 0.0);
+        let b = Vec3::new(-2.0, 0.0, 0.0);
+        for i in 0..=10 {
+            let t = i as f64 / 10.0;
+            assert_approx_eq::assert_approx_eq!(a.blend(&b, t).magnitude(), 2.0, 0.000001);
+        }
+        let end = a.blend(&b, 1.0);
+        assert_approx_eq::assert_approx_eq!(end.x, -2.0, 0.000001);
+        assert_approx_eq::assert_approx_eq!(end.y, 0.0, 0.000001);
+        assert_approx_eq::assert_approx_eq!(end.z, 0.0, 0.000001);
+    }
+
+    #[test]
+    fn test_blend_length() {
+        let a = Vec3::new(1.0, 0.0, 0.0);
+        let b = Vec3::new(0.0, 0.0, 3.0);
+        assert_approx_eq::assert_approx_eq!(a.blend(&b, 0.5).magnitude(), 2.0, 0.000001);
+        assert_eq!(Vec3::new(0.0, 0.0, 0.0).blend(&b, 0.5), Vec3::new(0.0, 0.0, 1.5));
+    }
+
+    #[test]
+    fn test_scalar_triple() {
+        let a = Vec3::new(1.0, 0.0, 0.0);
+        let b = Vec3::new(0.0, 2.0, 0.0);
+        let c = Vec3::new(0.0, 0.0, 3.0);
+        assert_eq!(a.scalar_triple(&b, &c), 6.0);
+        assert_eq!(a.scalar_triple(&c, &b), -6.0);
+    }
+
+    #[test]
+    fn test_vector_triple() {
+        let a = Vec3::new(1.0, 2.0, 3.0);
+        let b = Vec3::new(-2.0, 0.5, 4.0);
+        let c = Vec3::new(3.0, -1.0, 2.0);
+        let r = a.vector_triple(&b, &c);
+        let expected = b * a.dot(&c) - c * a.dot(&b);
+        assert_approx_eq::assert_approx_eq!(r.x, expected.x, 0.000001);
+        assert_approx_eq::assert_approx_eq!(r.y, expected.y, 0.000001);
+        assert_approx_eq::assert_approx_eq!(r.z, expected.z, 0.000001);
+    }
+
+    #[test]
+    fn test_le_bytes() {
+        let v = Vec3::new(1.0 / 3.0, -2.5e-300, f64::MAX);
+        let bytes = v.to_le_bytes();
+        assert_eq!(bytes.len(), 24);
+        assert_eq!(bytes[0..8], (1.0_f64 / 3.0).to_le_bytes());
+        assert_eq!(Vec3::from_le_bytes(&bytes).to_bits(), v.to_bits());
+    }
+
+    #[test]
+    fn test_wrap_angles() {
+        use std::f64::consts::PI;
+
+        let v = Vec3::new(3.0 * PI, 3.0 * PI, 3.0 * PI).wrap_angles();
+        for c in [v.x, v.y, v.z].iter() {
+            assert!(*c >= -PI && *c <= PI);
+        }
+        let v = Vec3::new(0.5, 2.0 * PI + 0.25, -4.0 * PI - 1.0).wrap_angles();
+        assert_approx_eq::assert_approx_eq!(v.x, 0.5, 0.000001);
+        assert_approx_eq::assert_approx_eq!(v.y, 0.25, 0.000001);
+        assert_approx_eq::assert_approx_eq!(v.z, -1.0, 0.000001);
+    }
+
+    #[test]
+    fn test_into_iter() {
+        let v = Vec3::new(1.0, 2.0, 3.0);
+        assert_eq!(v.into_iter().sum::<f64>(), 6.0);
+        assert_eq!(v.into_iter().map(|c| c * 2.0).collect::<Vec3>(), v * 2.0);
+    }
+
+    #[test]
+    fn test_from_iter() {
+        let v: Vec3 = (1..10).map(|i| i as f64).collect();
+        assert_eq!(v, Vec3::new(1.0, 2.0, 3.0));
+        let v: Vec3 = vec![4.0].into_iter().collect();
+        assert_eq!(v, Vec3::new(4.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_abs_floor_ceil_round() {
+        let v = Vec3::new(-1.5, 2.4, 3.6);
+        assert_eq!(v.abs(), Vec3::new(1.5, 2.4, 3.6));
+        assert_eq!(v.floor(), Vec3::new(-2.0, 2.0, 3.0));
+        assert_eq!(v.ceil(), Vec3::new(-1.0, 3.0, 4.0));
+        assert_eq!(v.round(), Vec3::new(-2.0, 2.0, 4.0));
+    }
+
+    #[test]
+    fn test_cmp_masks() {
+        let a = Vec3::new(1.0, 5.0, 3.0);
+        let b = Vec3::new(2.0, 4.0, 3.0);
+        assert_eq!(a.cmplt(&b), (true, false, false));
+        assert_eq!(a.cmpgt(&b), (false, true, false));
+        assert_eq!(a.cmpeq(&b), (false, false, true));
+    }
+
+    #[test]
+    fn test_all_any() {
+        let a = Vec3::new(1.0, 5.0, 3.0);
+        let b = Vec3::new(2.0, 4.0, 3.0);
+        assert!(any(a.cmplt(&b)));
+        assert!(!all(a.cmplt(&b)));
+        assert!(all(a.cmplt(&Vec3::new(10.0, 10.0, 10.0))));
+        assert!(!any(a.cmpgt(&Vec3::new(10.0, 10.0, 10.0))));
+    }
+
+    #[test]
+    fn test_barycentric() {
+        let a = Vec3::new(0.0, 0.0, 0.0);
+        let b = Vec3::new(2.0, 0.0, 0.0);
+        let c = Vec3::new(0.0, 2.0, 1.0);
+        assert_eq!(Vec3::barycentric(&a, &b, &c, 0.0, 0.0), a);
+        assert_eq!(Vec3::barycentric(&a, &b, &c, 1.0, 0.0), b);
+        assert_eq!(Vec3::barycentric(&a, &b, &c, 0.0, 1.0), c);
+        assert_eq!(Vec3::barycentric(&a, &b, &c, 0.5, 0.5), Vec3::new(1.0, 1.0, 0.5));
+    }
+
+    #[test]
+    fn test_compute_barycentric() {
+        let a = Vec3::new(1.0, 0.0, 0.0);
+        let b = Vec3::new(0.0, 1.0, 0.0);
+        let c = Vec3::new(0.0, 0.0, 1.0);
+        assert_eq!(Vec3::compute_barycentric(&a, &a, &b, &c), (1.0, 0.0, 0.0));
+        assert_eq!(Vec3::compute_barycentric(&b, &a, &b, &c), (0.0, 1.0, 0.0));
+        assert_eq!(Vec3::compute_barycentric(&c, &a, &b, &c), (0.0, 0.0, 1.0));
+
+        let p = Vec3::barycentric(&a, &b, &c, 0.2, 0.3);
+        let (wa, wb, wc) = Vec3::compute_barycentric(&p, &a, &b, &c);
+        assert_approx_eq::assert_approx_eq!(wa, 0.5, 0.000001);
+        assert_approx_eq::assert_approx_eq!(wb, 0.2, 0.000001);
+        assert_approx_eq::assert_approx_eq!(wc, 0.3, 0.000001);
+    }
+
+    #[test]
+    fn test_cosine_weighted_hemisphere() {
+        let normals = [
+            Vec3::new(0.0, 0.0, 1.0),
+            Vec3::new(1.0, 0.0, 0.0),
+            Vec3::new(-1.0, 2.0, 0.5).get_normalize()
+        ];
+        for n in normals.iter() {
+            let mut mean_cos = 0.0;
+            for i in 0..256 {
+                let d = Vec3::cosine_weighted_hemisphere(*n, Vec2::halton(i, 2, 3));
+                assert_approx_eq::assert_approx_eq!(d.magnitude(), 1.0, 0.000001);
+                assert!(d.dot(n) > 0.0);
+                mean_cos += d.dot(n) / 256.0;
+            }
+            // E[cos] = 2/3 for a cosine weighted distribution
+            assert_approx_eq::assert_approx_eq!(mean_cos, 2.0 / 3.0, 0.01);
+        }
+    }
+
+    #[test]
+    fn test_from_spherical() {
+        use std::f64::consts::PI;
+
+        let v = Vec3::from_spherical(2.0, 0.0, 1.0);
+        assert_approx_eq::assert_approx_eq!(v.y, 2.0, 0.000001);
+        let v = Vec3::from_spherical(2.0, PI / 2.0, PI / 2.0);
+        assert_approx_eq::assert_approx_eq!(v.x, 0.0, 0.000001);
+        assert_approx_eq::assert_approx_eq!(v.y, 0.0, 0.000001);
+        assert_approx_eq::assert_approx_eq!(v.z, 2.0, 0.000001);
+    }
+
+    #[test]
+    fn test_spherical_round_trip() {
+        let points = [
+            Vec3::new(1.0, 2.0, 3.0),
+            Vec3::new(-4.0, 0.5, 1.0),
+            Vec3::new(0.3, -0.7, -2.0)
+        ];
+        for p in points.iter() {
+            let (r, theta, phi) = p.to_spherical();
+            let back = Vec3::from_spherical(r, theta, phi);
+            assert_approx_eq::assert_approx_eq!(back.x, p.x, 0.000001);
+            assert_approx_eq::assert_approx_eq!(back.y, p.y, 0.000001);
+            assert_approx_eq::assert_approx_eq!(back.z, p.z, 0.000001);
+        }
+        assert_eq!(Vec3::new(0.0, 0.0, 0.0).to_spherical(), (0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_orthonormal_basis() {
+        let normals = [
+            Vec3::new(0.0, 0.0, 1.0),
+            Vec3::new(0.0, 0.0, -1.0),
+            Vec3::new(1.0, 0.0, 0.0),
+            Vec3::new(0.0, -1.0, 0.0),
+            Vec3::new(-1.0, 2.0, 0.5).get_normalize()
+        ];
+        for n in normals.iter() {
+            let (t, b) = n.orthonormal_basis();
+            assert_approx_eq::assert_approx_eq!(t.magnitude(), 1.0, 0.000001);
+            assert_approx_eq::assert_approx_eq!(b.magnitude(), 1.0, 0.000001);
+            assert_approx_eq::assert_approx_eq!(t.dot(&b), 0.0, 0.000001);
+            assert_approx_eq::assert_approx_eq!(t.dot(n), 0.0, 0.000001);
+            assert_approx_eq::assert_approx_eq!(b.dot(n), 0.0, 0.000001);
+
+            // Right-handed: t x b = n
+            let c = t.cross(&b);
+            assert_approx_eq::assert_approx_eq!(c.x, n.x, 0.000001);
+            assert_approx_eq::assert_approx_eq!(c.y, n.y, 0.000001);
+            assert_approx_eq::assert_approx_eq!(c.z, n.z, 0.000001);
+        }
+    }
+
+    #[test]
+    fn test_try_normalize() {
+        assert_eq!(Vec3::new(0.0, 0.0, 0.0).try_normalize(), None);
+        assert_eq!(Vec3::new(1e-20, 0.0, 0.0).try_normalize(), None);
+
+        let n = Vec3::new(3.0, 0.0, 4.0).try_normalize().unwrap();
+        assert_approx_eq::assert_approx_eq!(n.magnitude(), 1.0, 0.000001);
+        assert_approx_eq::assert_approx_eq!(n.x, 0.6, 0.000001);
+        assert_approx_eq::assert_approx_eq!(n.z, 0.8, 0.000001);
+    }
+
+    #[test]
+    fn test_align_to_ground() {
+        let v = Vec3::new(3.0, 0.0, 4.0);
+        let r = v.align_to_ground(Vec3::new(0.0, 1.0, 0.0));
+        assert_approx_eq::assert_approx_eq!(r.x, v.x, 0.000001);
+        assert_approx_eq::assert_approx_eq!(r.y, v.y, 0.000001);
+        assert_approx_eq::assert_approx_eq!(r.z, v.z, 0.000001);
+
+        // 45 degree slope rising toward +X
+        let slope = Vec3::new(-1.0, 1.0, 0.0).get_normalize();
+        let r = Vec3::new(2.0, 0.0, 0.0).align_to_ground(slope);
+        assert_approx_eq::assert_approx_eq!(r.magnitude(), 2.0, 0.000001);
+        assert_approx_eq::assert_approx_eq!(r.dot(&slope), 0.0, 0.000001);
+        assert_approx_eq::assert_approx_eq!(r.x, r.y, 0.000001);
+        assert!(r.y > 0.0);
+
+        // Walking across the slope stays horizontal
+        let r = Vec3::new(0.0, 0.0, 2.0).align_to_ground(slope);
+        assert_approx_eq::assert_approx_eq!(r.y, 0.0, 0.000001);
+        assert_approx_eq::assert_approx_eq!(r.z, 2.0, 0.000001);
+    }
+
+    #[test]
+    fn test_move_towards() {
+        let from = Vec3::new(1.0, 2.0, 3.0);
+        let target = Vec3::new(4.0, 6.0, 3.0);
+        assert_eq!(from.move_towards(&target, 10.0), target);
+        assert_eq!(from.move_towards(&target, 5.0), target);
+
+        let r = from.move_towards(&target, 1.0);
+        assert_approx_eq::assert_approx_eq!(r.x, 1.6, 0.000001);
+        assert_approx_eq::assert_approx_eq!(r.y, 2.8, 0.000001);
+        assert_approx_eq::assert_approx_eq!(r.z, 3.0, 0.000001);
+        assert_eq!(target.move_towards(&target, 0.0), target);
+    }
+
+    #[test]
+    fn test_clamp_magnitude() {
+        let v = Vec3::new(0.0, 3.0, 4.0);
+        assert_eq!(v.clamp_magnitude(10.0), v);
+        assert_eq!(v.clamp_magnitude(5.0), v);
+
+        let r = v.clamp_magnitude(2.5);
+        assert_approx_eq::assert_approx_eq!(r.magnitude(), 2.5, 0.000001);
+        assert_approx_eq::assert_approx_eq!(r.y, 1.5, 0.000001);
+        assert_approx_eq::assert_approx_eq!(r.z, 2.0, 0.000001);
+        assert_eq!(Vec3::new(0.0, 0.0, 0.0).clamp_magnitude(1.0), Vec3::new(0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_ease() {
+        use crate::math::Ease;
+
+        let a = Vec3::new(0.0, 0.0, 0.0);
+        let b = Vec3::new(4.0, 8.0, -4.0);
+        assert_eq!(a.ease(&b, 0.0, Ease::CubicInOut), a);
+        assert_eq!(a.ease(&b, 1.0, Ease::QuadIn), b);
+        assert_eq!(a.ease(&b, 0.5, Ease::Linear), Vec3::new(2.0, 4.0, -2.0));
+
+        let eased = a.ease(&b, 0.25, Ease::QuadIn);
+        let linear = a.ease(&b, 0.25, Ease::Linear);
+        assert!(eased.x < linear.x);
+        assert_eq!(eased, Vec3::new(0.25, 0.5, -0.25));
+    }
+
+    #[test]
+    fn test_min_max() {
+        let a = Vec3::new(1.0, -2.0, 3.0);
+        let b = Vec3::new(0.0, 5.0, 3.0);
+        assert_eq!(a.min(&b), Vec3::new(0.0, -2.0, 3.0));
+        assert_eq!(a.max(&b), Vec3::new(1.0, 5.0, 3.0));
+    }
+
+    #[test]
+    fn test_smoothstep() {
+        let e0 = Vec3::new(0.0, 0.0, 1.0);
+        let e1 = Vec3::new(1.0, 2.0, 3.0);
+        let x = Vec3::new(0.5, 2.0, 0.0);
+        assert_eq!(Vec3::smoothstep(&e0, &e1, &x), Vec3::new(0.5, 1.0, 0.0));
+        assert_eq!(Vec3::smootherstep(&e0, &e1, &x), Vec3::new(0.5, 1.0, 0.0));
+        assert_eq!(Vec3::smoothstep(&e0, &e1, &Vec3::new(0.0, 1.0, 3.0)), Vec3::new(0.0, 0.5, 1.0));
+    }
+
+    #[test]
+    fn test_key_along() {
+        use crate::math::total_cmp_f64;
+
+        let mut points = [
+            Vec3::new(3.0, 0.0, 1.0),
+            Vec3::new(-2.0, 5.0, 0.0),
+            Vec3::new(0.5, -1.0, 9.0)
+        ];
+        let x = Vec3::new(2.0, 0.0, 0.0);
+        assert_eq!(points[0].key_along(x), 3.0);
+        points.sort_by(|a, b| total_cmp_f64(&a.key_along(x), &b.key_along(x)));
+        assert_eq!(points[0].x, -2.0);
+        assert_eq!(points[1].x, 0.5);
+        assert_eq!(points[2].x, 3.0);
+
+        let diagonal = Vec3::new(1.0, 1.0, 0.0);
+        assert_approx_eq::assert_approx_eq!(Vec3::new(1.0, 1.0, 5.0).key_along(diagonal), 2.0_f64.sqrt(), 0.000001);
+        assert!(points[0].key_along(Vec3::new(0.0, 0.0, 0.0)).is_nan());
+    }
+
+    #[test]
+    fn test_lexicographic_cmp() {
+        use std::cmp::Ordering;
+
+        let a = Vec3::new(1.0, 2.0, 3.0);
+        assert_eq!(a.lexicographic_cmp(&a), Ordering::Equal);
+        assert_eq!(a.lexicographic_cmp(&Vec3::new(1.0, 2.0, 4.0)), Ordering::Less);
+        assert_eq!(a.lexicographic_cmp(&Vec3::new(0.0, 9.0, 9.0)), Ordering::Greater);
+
+        let mut points = [
+            Vec3::new(1.0, f64::NAN, 0.0),
+            Vec3::new(1.0, 0.0, 2.0),
+            Vec3::new(-1.0, 5.0, 5.0),
+            Vec3::new(1.0, 0.0, -2.0)
+        ];
+        points.sort_by(|a, b| a.lexicographic_cmp(b));
+        assert_eq!(points[0], Vec3::new(-1.0, 5.0, 5.0));
+        assert_eq!(points[1], Vec3::new(1.0, 0.0, -2.0));
+        assert_eq!(points[2], Vec3::new(1.0, 0.0, 2.0));
+        assert!(points[3].y.is_nan());
+
+        // Sorting again does not move anything
+        let before = points.map(|p| p.to_bits());
+        points.sort_by(|a, b| a.lexicographic_cmp(b));
+        assert_eq!(points.map(|p| p.to_bits()), before);
+    }
+
+    #[test]
+    fn test_sum() {
+        let vecs = [Vec3::new(1.0, 2.0, 3.0), Vec3::new(-3.0, 0.5, 0.0), Vec3::new(0.0, 1.5, -1.0)];
+        assert_eq!(vecs.iter().copied().sum::<Vec3>(), Vec3::new(-2.0, 4.0, 2.0));
+        assert_eq!(std::iter::empty::<Vec3>().sum::<Vec3>(), Vec3::new(0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_centroid() {
+        let points = [
+            Vec3::new(1.0, 0.0, 0.0),
+            Vec3::new(-1.0, 0.0, 0.0),
+            Vec3::new(0.0, 2.0, -3.0),
+            Vec3::new(0.0, -2.0, 3.0)
+        ];
+        assert_eq!(Vec3::centroid(&points), Vec3::new(0.0, 0.0, 0.0));
+        assert_eq!(Vec3::centroid(&points[..3]), Vec3::new(0.0, 2.0 / 3.0, -1.0));
+        assert_eq!(Vec3::centroid(&[]), Vec3::new(0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_is_finite_is_nan() {
+        assert!(Vec3::new(1.0, -2.0, 1e300).is_finite());
+        assert!(!Vec3::new(0.0, 0.0, f64::INFINITY).is_finite());
+        assert!(!Vec3::new(f64::NAN, 0.0, 0.0).is_finite());
+        assert!(Vec3::new(0.0, f64::NAN, 0.0).is_nan());
+        assert!(!Vec3::new(f64::NEG_INFINITY, 0.0, 1.0).is_nan());
+        assert!(!Vec3::new(1.0, 2.0, 3.0).is_nan());
+    }
+
+    #[test]
+    fn test_catmull_rom() {
+        let p0 = Vec3::new(-1.0, 0.0, 0.0);
+        let p1 = Vec3::new(0.0, 1.0, 0.0);
+        let p2 = Vec3::new(2.0, 1.0, 1.0);
+        let p3 = Vec3::new(3.0, -1.0, 2.0);
+        assert_eq!(Vec3::catmull_rom(p0, p1, p2, p3, 0.0), p1);
+        assert_eq!(Vec3::catmull_rom(p0, p1, p2, p3, 1.0), p2);
+
+        // Evenly spaced collinear points give a linear interpolation
+        let m = Vec3::catmull_rom(
+            Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0),
+            Vec3::new(2.0, 0.0, 0.0), Vec3::new(3.0, 0.0, 0.0), 0.25);
+        assert_approx_eq::assert_approx_eq!(m.x, 1.25, 0.000001);
+        assert_approx_eq::assert_approx_eq!(m.y, 0.0, 0.000001);
+    }
+
+    #[test]
+    fn test_cubic_bezier() {
+        let p0 = Vec3::new(0.0, 0.0, 0.0);
+        let p1 = Vec3::new(1.0, 2.0, 0.0);
+        let p2 = Vec3::new(3.0, 2.0, 1.0);
+        let p3 = Vec3::new(4.0, 0.0, 1.0);
+        assert_eq!(Vec3::cubic_bezier(p0, p1, p2, p3, 0.0), p0);
+        assert_eq!(Vec3::cubic_bezier(p0, p1, p2, p3, 1.0), p3);
+        assert_eq!(Vec3::cubic_bezier(p0, p1, p2, p3, 0.5), Vec3::new(2.0, 1.5, 0.5));
+    }
+
+    #[test]
+    fn test_cubic_bezier_tangent() {
+        let p0 = Vec3::new(0.0, 0.0, 0.0);
+        let p1 = Vec3::new(1.0, 2.0, 0.0);
+        let p2 = Vec3::new(3.0, 2.0, 1.0);
+        let p3 = Vec3::new(4.0, 0.0, 1.0);
+        let start = Vec3::cubic_bezier_tangent(p0, p1, p2, p3, 0.0);
+        assert_eq!(start, (p1 - p0) * 3.0);
+        assert_approx_eq::assert_approx_eq!(start.get_normalize().dot(&(p1 - p0).get_normalize()), 1.0, 0.000001);
+        assert_eq!(Vec3::cubic_bezier_tangent(p0, p1, p2, p3, 1.0), (p3 - p2) * 3.0);
+
+        // Matches a finite difference of the curve
+        let h = 1e-6;
+        let fd = (Vec3::cubic_bezier(p0, p1, p2, p3, 0.3 + h) - Vec3::cubic_bezier(p0, p1, p2, p3, 0.3 - h)) / (2.0 * h);
+        let d = Vec3::cubic_bezier_tangent(p0, p1, p2, p3, 0.3);
+        assert_approx_eq::assert_approx_eq!(d.x, fd.x, 0.0001);
+        assert_approx_eq::assert_approx_eq!(d.y, fd.y, 0.0001);
+        assert_approx_eq::assert_approx_eq!(d.z, fd.z, 0.0001);
+    }
+
+    #[test]
+    fn test_const_new() {
+        const ORIGIN: Vec3 = Vec3::new(0.0, 0.0, 0.0);
+        static UP: Vec3 = Vec3::new(0.0, 1.0, 0.0);
+        assert_eq!(ORIGIN + UP, Vec3::new(0.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn test_step() {
+        let p = Vec3::new(1.0, 1.0, 1.0);
+        for dir in [Vec3::new(10.0, 0.0, 0.0), Vec3::new(0.001, -0.002, 0.003)].iter() {
+            let r = p.step(dir, 2.5);
+            assert_approx_eq::assert_approx_eq!((r - p).magnitude(), 2.5, 0.000001);
+            assert_approx_eq::assert_approx_eq!((r - p).get_normalize().dot(&dir.get_normalize()), 1.0, 0.000001);
+        }
+        assert_eq!(p.step(&Vec3::new(0.0, 1.0, 0.0), -1.0), Vec3::new(1.0, 0.0, 1.0));
+        assert_eq!(p.step(&Vec3::new(0.0, 0.0, 0.0), 3.0), p);
+    }
+
+    #[test]
+    fn test_bit_eq() {
+        let zero = Vec3::new(0.0, 0.0, 0.0);
+        let negative_zero = Vec3::new(-0.0, 0.0, 0.0);
+        assert_eq!(zero, negative_zero);
+        assert!(!zero.bit_eq(&negative_zero));
+        assert!(zero.bit_eq(&zero));
+
+        let nan = Vec3::new(f64::NAN, 1.0, 2.0);
+        assert_ne!(nan, nan);
+        assert!(nan.bit_eq(&nan));
+
+        let v = Vec3::new(-0.0, 1.5, -2.25);
+        assert!(Vec3::from_le_bytes(&v.to_le_bytes()).bit_eq(&v));
+    }
+
+    #[test]
+    fn test_mul_add() {
+        let v = Vec3::new(1.5, -2.0, 0.1);
+        let a = Vec3::new(3.0, 0.25, -7.0);
+        let r = v.mul_add(0.3, &a);
+        let naive = v * 0.3 + a;
+        assert_approx_eq::assert_approx_eq!(r.x, naive.x, 1e-12);
+        assert_approx_eq::assert_approx_eq!(r.y, naive.y, 1e-12);
+        assert_approx_eq::assert_approx_eq!(r.z, naive.z, 1e-12);
+
+        // The fused version keeps the tiny residual the naive one rounds away
+        let e = f64::EPSILON;
+        let x = Vec3::new(1.0 + e, 0.0, 0.0);
+        let fused = x.mul_add(1.0 - e, &Vec3::new(-1.0, 0.0, 0.0));
+        assert_eq!(fused.x, -e * e);
+        assert_eq!((x * (1.0 - e) + Vec3::new(-1.0, 0.0, 0.0)).x, 0.0);
+    }
+
+    #[test]
+    fn test_tuple() {
+        let v = Vec3::new(1.5, -2.0, 3.0);
+        let (x, y, z) = v.into_tuple();
+        assert_eq!((x, y, z), (1.5, -2.0, 3.0));
+        assert_eq!(Vec3::from(v.into_tuple()), v);
+        let w: Vec3 = (3.0, 4.0, 5.0).into();
+        assert_eq!(w, Vec3::new(3.0, 4.0, 5.0));
+    }
+
+    #[test]
+    fn test_cross_right_hand_rule() {
+        let x = Vec3::new(1.0, 0.0, 0.0);
+        let y = Vec3::new(0.0, 1.0, 0.0);
+        let z = Vec3::new(0.0, 0.0, 1.0);
+        assert_eq!(x.cross(&y), z);
+        assert_eq!(y.cross(&z), x);
+        assert_eq!(z.cross(&x), y);
+        assert_eq!(y.cross(&x), -z);
+        assert_eq!(x.cross(&x), Vec3::new(0.0, 0.0, 0.0));
+
+        let a = Vec3::new(1.0, 2.0, 3.0);
+        let b = Vec3::new(-2.0, 0.5, 4.0);
+        let c = a.cross(&b);
+        assert_approx_eq::assert_approx_eq!(c.dot(&a), 0.0, 0.000001);
+        assert_approx_eq::assert_approx_eq!(c.dot(&b), 0.0, 0.000001);
+        // Not to be confused with the componentwise remainder
+        assert_ne!(a % b, c);
+    }
+
+    #[test]
+    fn test_to_string_precision() {
+        let v = Vec3::new(1.23456789, -0.5, 100.0);
+        assert_eq!(v.to_string_precision(0), "Vec3(x: 1, y: -0, z: 100)");
+        assert_eq!(v.to_string_precision(2), "Vec3(x: 1.23, y: -0.50, z: 100.00)");
+        assert_eq!(v.to_string_precision(2), v.to_string());
+        assert_eq!(v.to_string_precision(6), "Vec3(x: 1.234568, y: -0.500000, z: 100.000000)");
+    }
+
+    #[test]
+    fn test_lower_exp() {
+        let v = Vec3::new(1e-10, -2500.0, 0.0);
+        assert_eq!(format!("{:e}", v), "Vec3(x: 1e-10, y: -2.5e3, z: 0e0)");
+        assert_eq!(format!("{:.2e}", v), "Vec3(x: 1.00e-10, y: -2.50e3, z: 0.00e0)");
+        assert_eq!(v.to_string(), "Vec3(x: 0.00, y: -2500.00, z: 0.00)");
+    }
+
+    #[test]
+    fn test_refract() {
+        let n = Vec3::new(0.0, 1.0, 0.0);
+        let d = Vec3::new(1.0, -1.0, 0.0).get_normalize();
+        let r = d.refract(&n, 1.0).unwrap();
+        assert_approx_eq::assert_approx_eq!(r.x, d.x, 0.000001);
+        assert_approx_eq::assert_approx_eq!(r.y, d.y, 0.000001);
+
+        // Into glass the ray bends toward the normal
+        let r = d.refract(&n, 1.0 / 1.5).unwrap();
+        assert_approx_eq::assert_approx_eq!(r.magnitude(), 1.0, 0.000001);
+        assert_approx_eq::assert_approx_eq!(r.x, d.x / 1.5, 0.000001);
+
+        // Out of glass past the critical angle
+        let steep = Vec3::new(0.9, -0.1, 0.0).get_normalize();
+        assert_eq!(steep.refract(&n, 1.5), None);
+    }
 }
@@ -10,6 +10,7 @@ use std::ops::{
     Neg
 };
 use std::cmp::{PartialEq};
+use std::convert::TryInto;
 use super::vector3::Vec3;
 use super::quaternions::Quaternion;
 
@@ -34,6 +35,21 @@ impl std::fmt::Display for Vec4 {
     }
 }
 
+// Scientific notation, `{:e}` or `{:.3e}` apply to every component
+impl std::fmt::LowerExp for Vec4 {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Vec4(x: ")?;
+        std::fmt::LowerExp::fmt(&self.x, f)?;
+        write!(f, ", y: ")?;
+        std::fmt::LowerExp::fmt(&self.y, f)?;
+        write!(f, ", z: ")?;
+        std::fmt::LowerExp::fmt(&self.z, f)?;
+        write!(f, ", w: ")?;
+        std::fmt::LowerExp::fmt(&self.w, f)?;
+        write!(f, ")")
+    }
+}
+
 impl PartialEq<Vec4> for Vec4 {
     fn eq(&self, other: &Vec4) -> bool {
         self.x == other.x &&
@@ -44,15 +60,30 @@ impl PartialEq<Vec4> for Vec4 {
 }
 
 impl Vec4 {
-    pub fn new(x: f64, y: f64, z: f64, w: f64) -> Vec4 {
+    pub const fn new(x: f64, y: f64, z: f64, w: f64) -> Vec4 {
         Vec4 {
-            x: x,
-            y: y,
-            z: z,
-            w: w
+            x,
+            y,
+            z,
+            w
         }
     }
 
+    pub fn into_tuple(self) -> (f64, f64, f64, f64) {
+        (self.x, self.y, self.z, self.w)
+    }
+
+    // Same layout as Display with `digits` decimals instead of 2
+    pub fn to_string_precision(&self, digits: usize) -> String {
+        format!(
+            "Vec4(x: {:.*}, y: {:.*}, z: {:.*}, w: {:.*})",
+            digits, self.x,
+            digits, self.y,
+            digits, self.z,
+            digits, self.w
+        )
+    }
+
     pub fn to_vec3(&self) -> Vec3 {
         Vec3 {
             x: self.x / self.w,
@@ -61,6 +92,14 @@ impl Vec4 {
         }
     }
 
+    // Perspective divide like to_vec3, None for points at infinity (w close to 0)
+    pub fn to_vec3_checked(&self) -> Option<Vec3> {
+        if self.w.abs() < f64::EPSILON {
+            return None;
+        }
+        Some(self.to_vec3())
+    }
+
     pub fn to_pure_vec3(&self) -> Vec3 {
         Vec3 {
             x: self.x,
@@ -84,9 +123,33 @@ impl Vec4 {
         }
     }
 
+    // True when no component is infinite or NaN
+    pub fn is_finite(&self) -> bool {
+        self.x.is_finite() && self.y.is_finite() && self.z.is_finite() && self.w.is_finite()
+    }
+
+    // True when any component is NaN
+    pub fn is_nan(&self) -> bool {
+        self.x.is_nan() || self.y.is_nan() || self.z.is_nan() || self.w.is_nan()
+    }
+
+    // Same scaling as normalize, None for degenerate (near zero) vectors
+    pub fn try_normalize(&self) -> Option<Vec4> {
+        let norm = self.norm();
+        if norm < f64::EPSILON {
+            return None;
+        }
+        Some(*self * (1.0 / norm))
+    }
+
+    // Average of all four components, w included
+    pub fn midpoint(&self, other: &Vec4) -> Vec4 {
+        (*self + *other) * 0.5
+    }
+
     pub fn get_normliaze(&self) -> Vec4 {
         let norm = self.norm();
-        return if norm > 0.0 {
+        if norm > 0.0 {
             let mag = 1.0 / norm;
             Vec4 {
                 x: self.x * mag,
@@ -108,6 +171,21 @@ impl Vec4 {
         }
     }
 
+    // Little-endian wire format: x, y, z, w as f64
+    pub fn to_le_bytes(&self) -> [u8; 32] {
+        let mut r = [0; 32];
+        r[0..8].copy_from_slice(&self.x.to_le_bytes());
+        r[8..16].copy_from_slice(&self.y.to_le_bytes());
+        r[16..24].copy_from_slice(&self.z.to_le_bytes());
+        r[24..32].copy_from_slice(&self.w.to_le_bytes());
+        r
+    }
+
+    pub fn from_le_bytes(bytes: &[u8; 32]) -> Vec4 {
+        let f = |i: usize| f64::from_le_bytes(bytes[i..i + 8].try_into().unwrap());
+        Vec4::new(f(0), f(8), f(16), f(24))
+    }
+
     pub fn convert_to_unit_norm(&mut self) {
         let angle = self.w * std::f64::consts::PI / 180.0;
         self.normalize();
@@ -127,16 +205,11 @@ impl Vec4 {
         }
     }
 
+    // Quaternion inverse (w being the scalar part): q* / |q|^2
+    // Unlike norm, |q| includes the w component here
     pub fn inverse(&self) -> Vec4 {
-        let norm = self.norm();
-        let conj = self.conjugate();
-        let v3 = Vec3::new(conj.x, conj.y, conj.z) * (1.0 / (norm * norm));
-        Vec4 {
-            x: v3.x,
-            y: v3.y,
-            z: v3.z,
-            w: conj.w * norm
-        }
+        let norm_squared = self.x * self.x + self.y * self.y + self.z * self.z + self.w * self.w;
+        self.conjugate() * (1.0 / norm_squared)
     }
 
     pub fn rotate(&self, rhs: &Vec4) -> Vec3 {
@@ -162,6 +235,14 @@ impl Add<Vec4> for Vec4 {
     }
 }
 
+impl Add<&Vec4> for &Vec4 {
+    type Output = Vec4;
+
+    fn add(self, other: &Vec4) -> Vec4 {
+        *self + *other
+    }
+}
+
 impl AddAssign<Vec4> for Vec4 {
     fn add_assign(&mut self, other: Vec4) {
         self.x += other.x;
@@ -171,6 +252,28 @@ impl AddAssign<Vec4> for Vec4 {
     }
 }
 
+// Adding a Vec3 only moves x, y and z, the w component is kept
+impl Add<Vec3> for Vec4 {
+    type Output = Vec4;
+
+    fn add(self, other: Vec3) -> Vec4 {
+        Vec4::new(
+            self.x + other.x,
+            self.y + other.y,
+            self.z + other.z,
+            self.w
+        )
+    }
+}
+
+impl AddAssign<Vec3> for Vec4 {
+    fn add_assign(&mut self, other: Vec3) {
+        self.x += other.x;
+        self.y += other.y;
+        self.z += other.z;
+    }
+}
+
 impl Sub<Vec4> for Vec4 {
     type Output = Vec4;
 
@@ -184,6 +287,14 @@ impl Sub<Vec4> for Vec4 {
     }
 }
 
+impl Sub<&Vec4> for &Vec4 {
+    type Output = Vec4;
+
+    fn sub(self, other: &Vec4) -> Vec4 {
+        *self - *other
+    }
+}
+
 impl SubAssign<Vec4> for Vec4 {
     fn sub_assign(&mut self, other: Vec4) {
         self.x -= other.x;
@@ -193,6 +304,36 @@ impl SubAssign<Vec4> for Vec4 {
     }
 }
 
+// Scalar multiplication scales every component (w included)
+impl Mul<f64> for Vec4 {
+    type Output = Vec4;
+
+    fn mul(self, s: f64) -> Vec4 {
+        Vec4::new(
+            self.x * s,
+            self.y * s,
+            self.z * s,
+            self.w * s
+        )
+    }
+}
+
+impl Mul<f64> for &Vec4 {
+    type Output = Vec4;
+
+    fn mul(self, s: f64) -> Vec4 {
+        *self * s
+    }
+}
+
+impl Mul<Vec4> for f64 {
+    type Output = Vec4;
+
+    fn mul(self, v: Vec4) -> Vec4 {
+        v * self
+    }
+}
+
 /**
  * Cross product of two vectors with 4 dimensions does not exist.
  * It will be just a simple multiplication of a vector4 with vec3.
@@ -258,10 +399,43 @@ impl Neg for Vec4 {
     }
 }
 
+impl From<(f64, f64, f64, f64)> for Vec4 {
+    fn from(t: (f64, f64, f64, f64)) -> Vec4 {
+        Vec4::new(t.0, t.1, t.2, t.3)
+    }
+}
+
+// Iterates over x, y, z, w
+impl IntoIterator for Vec4 {
+    type Item = f64;
+    type IntoIter = std::array::IntoIter<f64, 4>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIterator::into_iter([self.x, self.y, self.z, self.w])
+    }
+}
+
+// Takes the first four values, missing ones are set to 0
+impl std::iter::FromIterator<f64> for Vec4 {
+    fn from_iter<I: IntoIterator<Item = f64>>(iter: I) -> Vec4 {
+        let mut it = iter.into_iter();
+        let mut next = || it.next().unwrap_or(0.0);
+        Vec4::new(next(), next(), next(), next())
+    }
+}
+
+// Componentwise sum starting from zero
+impl std::iter::Sum<Vec4> for Vec4 {
+    fn sum<I: Iterator<Item = Vec4>>(iter: I) -> Vec4 {
+        iter.fold(Vec4::new(0.0, 0.0, 0.0, 0.0), |acc, v| acc + v)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::Vec3;
     use super::Vec4;
+    use super::Quaternion;
 
     #[test]
     fn test_create_vec4() {
@@ -417,4 +591,156 @@ mod test {
         let v1 = Vec4::new(1.0519, 2.10, 3.33, 4.01);
         assert_eq!(format!("{}", v1), "Vec4(x: 1.05, y: 2.10, z: 3.33, w: 4.01)");
     }
+
+    #[test]
+    fn test_mul_scalar() {
+        let v1 = Vec4::new(1.0, 2.0, 3.0, 4.0);
+        assert_eq!(v1 * 2.0, Vec4::new(2.0, 4.0, 6.0, 8.0));
+    }
+
+    #[test]
+    #[allow(clippy::op_ref)]
+    fn test_ref_ops() {
+        let v1 = Vec4::new(1.0, 2.0, 3.0, 4.0);
+        let v2 = Vec4::new(4.0, 3.0, 2.0, 1.0);
+        assert_eq!(&v1 + &v2, v1 + v2);
+        assert_eq!(&v1 - &v2, v1 - v2);
+        assert_eq!(&v1 * 2.0, v1 * 2.0);
+    }
+
+    #[test]
+    #[allow(clippy::op_ref)]
+    fn test_ref_ops_accumulate() {
+        let points = [
+            Vec4::new(1.0, 2.0, 3.0, 1.0),
+            Vec4::new(4.0, 5.0, 6.0, 1.0)
+        ];
+        let mut sum = Vec4::new(0.0, 0.0, 0.0, 0.0);
+        for p in points.iter() {
+            sum = &sum + &(p * 2.0);
+            sum = &sum - p;
+        }
+        assert_eq!(sum, Vec4::new(5.0, 7.0, 9.0, 2.0));
+    }
+
+    #[test]
+    fn test_scalar_left_mul() {
+        let v = Vec4::new(1.0, -2.0, 3.5, 1.0);
+        assert_eq!(2.0 * v, v * 2.0);
+        assert_eq!(2.0 * v, Vec4::new(2.0, -4.0, 7.0, 2.0));
+    }
+
+    #[test]
+    fn test_le_bytes() {
+        let v = Vec4::new(1.0 / 3.0, -2.0, 1e-10, 7.25);
+        let bytes = v.to_le_bytes();
+        assert_eq!(bytes.len(), 32);
+        assert_eq!(bytes[24..32], 7.25_f64.to_le_bytes());
+        assert_eq!(Vec4::from_le_bytes(&bytes), v);
+    }
+
+    #[test]
+    fn test_add_vec3() {
+        let v1 = Vec4::new(1.0, 2.0, 3.0, 4.0);
+        let v2 = Vec3::new(1.0, 1.0, 1.0);
+        assert_eq!(v1 + v2, Vec4::new(2.0, 3.0, 4.0, 4.0));
+    }
+
+    #[test]
+    fn test_add_assign_vec3() {
+        let mut v1 = Vec4::new(1.0, 2.0, 3.0, 4.0);
+        v1 += Vec3::new(1.0, 1.0, 1.0);
+        assert_eq!(v1, Vec4::new(2.0, 3.0, 4.0, 4.0));
+    }
+
+    #[test]
+    fn test_iter() {
+        let v: Vec4 = (1..10).map(|i| i as f64).collect();
+        assert_eq!(v, Vec4::new(1.0, 2.0, 3.0, 4.0));
+        assert_eq!(v.into_iter().sum::<f64>(), 10.0);
+    }
+
+    #[test]
+    fn test_inverse() {
+        let as_quaternion = |v: Vec4| Quaternion::new(v.to_pure_vec3(), v.w);
+        let v = Vec4::new(1.0, 2.0, 3.0, 4.0);
+        let r = as_quaternion(v) * as_quaternion(v.inverse());
+        assert_approx_eq::assert_approx_eq!(r.v.x, 0.0, 0.000001);
+        assert_approx_eq::assert_approx_eq!(r.v.y, 0.0, 0.000001);
+        assert_approx_eq::assert_approx_eq!(r.v.z, 0.0, 0.000001);
+        assert_approx_eq::assert_approx_eq!(r.s, 1.0, 0.000001);
+    }
+
+    #[test]
+    fn test_try_normalize() {
+        assert_eq!(Vec4::new(0.0, 0.0, 0.0, 0.0).try_normalize(), None);
+
+        let n = Vec4::new(0.0, 3.0, 4.0, 0.0).try_normalize().unwrap();
+        assert_approx_eq::assert_approx_eq!(n.norm(), 1.0, 0.000001);
+        assert_approx_eq::assert_approx_eq!(n.y, 0.6, 0.000001);
+        assert_approx_eq::assert_approx_eq!(n.z, 0.8, 0.000001);
+    }
+
+    #[test]
+    fn test_sum() {
+        let vecs = [
+            Vec4::new(1.0, 2.0, 3.0, 1.0),
+            Vec4::new(-3.0, 0.5, 0.0, 1.0),
+            Vec4::new(0.0, 1.5, -1.0, 0.5)
+        ];
+        assert_eq!(vecs.iter().copied().sum::<Vec4>(), Vec4::new(-2.0, 4.0, 2.0, 2.5));
+    }
+
+    #[test]
+    fn test_is_finite_is_nan() {
+        assert!(Vec4::new(1.0, -2.0, 3.0, 1.0).is_finite());
+        assert!(!Vec4::new(0.0, 0.0, 0.0, f64::INFINITY).is_finite());
+        assert!(Vec4::new(0.0, 0.0, 0.0, f64::NAN).is_nan());
+        assert!(!Vec4::new(f64::INFINITY, 0.0, 0.0, 0.0).is_nan());
+    }
+
+    #[test]
+    fn test_const_new() {
+        const POINT: Vec4 = Vec4::new(1.0, 2.0, 3.0, 1.0);
+        assert_eq!(POINT.w, 1.0);
+        assert_eq!(POINT.to_pure_vec3(), Vec3::new(1.0, 2.0, 3.0));
+    }
+
+    #[test]
+    fn test_tuple() {
+        let v = Vec4::new(1.5, -2.0, 3.0, 1.0);
+        assert_eq!(v.into_tuple(), (1.5, -2.0, 3.0, 1.0));
+        assert_eq!(Vec4::from(v.into_tuple()), v);
+        let w: Vec4 = (3.0, 4.0, 5.0, 0.0).into();
+        assert_eq!(w, Vec4::new(3.0, 4.0, 5.0, 0.0));
+    }
+
+    #[test]
+    fn test_to_vec3_checked() {
+        assert_eq!(Vec4::new(2.0, 4.0, 6.0, 2.0).to_vec3_checked(), Some(Vec3::new(1.0, 2.0, 3.0)));
+        assert_eq!(Vec4::new(1.0, 2.0, 3.0, 0.0).to_vec3_checked(), None);
+        assert_eq!(Vec4::new(1.0, 2.0, 3.0, -0.0).to_vec3_checked(), None);
+        assert!(!Vec4::new(1.0, 2.0, 3.0, 0.0).to_vec3().is_finite());
+    }
+
+    #[test]
+    fn test_to_string_precision() {
+        let v = Vec4::new(1.23456789, -0.5, 100.0, 1.0);
+        assert_eq!(v.to_string_precision(0), "Vec4(x: 1, y: -0, z: 100, w: 1)");
+        assert_eq!(v.to_string_precision(2), v.to_string());
+        assert_eq!(v.to_string_precision(6), "Vec4(x: 1.234568, y: -0.500000, z: 100.000000, w: 1.000000)");
+    }
+
+    #[test]
+    fn test_lower_exp() {
+        let v = Vec4::new(1e-10, 1e20, -3.0, 1.0);
+        assert_eq!(format!("{:e}", v), "Vec4(x: 1e-10, y: 1e20, z: -3e0, w: 1e0)");
+    }
+
+    #[test]
+    fn test_midpoint() {
+        let a = Vec4::new(0.0, 0.0, 0.0, 1.0);
+        let b = Vec4::new(2.0, 4.0, -6.0, 0.0);
+        assert_eq!(a.midpoint(&b), Vec4::new(1.0, 2.0, -3.0, 0.5));
+    }
 }